@@ -0,0 +1,154 @@
+//! Flag parsing shared by the binaries of every day.
+
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Error, ErrorKind};
+use std::str::FromStr;
+
+/// The values following one flag on the command line.
+pub struct Values<'a> {
+    flag: &'a str,
+    args: &'a mut dyn Iterator<Item = String>,
+}
+
+impl Values<'_> {
+    pub fn flag(&self) -> &str {
+        self.flag
+    }
+
+    /// The next argument, which must be there.
+    pub fn value(&mut self) -> Result<String, Error> {
+        self.args.next().ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("`{}` expects a value", self.flag),
+            )
+        })
+    }
+
+    /// The next argument read as a `T`.
+    pub fn parse<T>(&mut self) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self.value()?;
+        value.parse().map_err(|err| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("invalid value `{}` for `{}`: {}", value, self.flag, err),
+            )
+        })
+    }
+}
+
+/// Splits `args` into flags and positional arguments.
+///
+/// Every argument starting with `--` is handed to `on_flag` with the
+/// [`Values`] to take its own values from; `on_flag` returns `false` for a
+/// flag it does not know, which is an error. The other arguments are
+/// returned in order.
+pub fn parse<I, F>(args: I, mut on_flag: F) -> Result<Vec<String>, Error>
+where
+    I: IntoIterator<Item = String>,
+    F: FnMut(&mut Values) -> Result<bool, Error>,
+{
+    let mut args = args.into_iter();
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            positional.push(arg);
+            continue;
+        }
+        let mut values = Values {
+            flag: &arg,
+            args: &mut args,
+        };
+        if !on_flag(&mut values)? {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("unknown flag `{}`", arg),
+            ));
+        }
+    }
+    Ok(positional)
+}
+
+/// The one optional input path among `positional`, or `default`.
+pub fn input_path(positional: Vec<String>, default: &str) -> Result<String, Error> {
+    let mut positional = positional.into_iter();
+    match (positional.next(), positional.next()) {
+        (path, None) => Ok(path.unwrap_or_else(|| default.to_string())),
+        (_, Some(extra)) => Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("unexpected argument `{}`", extra),
+        )),
+    }
+}
+
+pub fn read_input(path: &str) -> Result<String, Error> {
+    fs::read_to_string(path)
+}
+
+/// `path` opened for reading line by line, or stdin for `-`.
+pub fn open_reader(path: &str) -> Result<Box<dyn BufRead>, Error> {
+    if path == "-" {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        Ok(Box::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_flags_and_positionals() {
+        let mut verbose = false;
+        let mut name = None;
+        let mut counts: Vec<usize> = Vec::new();
+        let positional = parse(
+            args("--count 79 input.txt --verbose --name x --count 13"),
+            |values| {
+                match values.flag() {
+                    "--verbose" => verbose = true,
+                    "--name" => name = Some(values.value()?),
+                    "--count" => counts.push(values.parse()?),
+                    _ => return Ok(false),
+                }
+                Ok(true)
+            },
+        )
+        .unwrap();
+        assert_eq!(positional, vec!["input.txt"]);
+        assert!(verbose);
+        assert_eq!(name.as_deref(), Some("x"));
+        assert_eq!(counts, vec![79, 13]);
+        assert_eq!(input_path(positional, "default").unwrap(), "input.txt");
+        assert_eq!(input_path(Vec::new(), "default").unwrap(), "default");
+    }
+
+    #[test]
+    fn test_errors() {
+        let known = |values: &mut Values| match values.flag() {
+            "--count" => values.parse::<usize>().map(|_| true),
+            _ => Ok(false),
+        };
+        let message = |line: &str| parse(args(line), known).unwrap_err().to_string();
+        assert_eq!(message("--count"), "`--count` expects a value");
+        assert_eq!(
+            message("--count x"),
+            "invalid value `x` for `--count`: invalid digit found in string"
+        );
+        assert_eq!(message("--bogus"), "unknown flag `--bogus`");
+        assert_eq!(
+            input_path(args("a b"), "default").unwrap_err().to_string(),
+            "unexpected argument `b`"
+        );
+    }
+}
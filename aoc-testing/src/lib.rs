@@ -0,0 +1,51 @@
+//! Helpers shared by the randomized tests of every day.
+
+use std::ops::Range;
+
+/// A fixed linear congruential generator, so randomized tests see the same
+/// cases on every run.
+pub struct Lcg(u64);
+
+impl Lcg {
+    pub fn new(seed: u64) -> Self {
+        Lcg(seed)
+    }
+
+    /// The next number in `0..bound`.
+    pub fn below(&mut self, bound: usize) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 33) as usize % bound
+    }
+
+    /// A range starting in `0..start_bound` whose length lies in `lengths`.
+    pub fn range(&mut self, start_bound: usize, lengths: Range<usize>) -> Range<usize> {
+        let start = self.below(start_bound);
+        start..start + lengths.start + self.below(lengths.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeatable_and_bounded() {
+        let draws = |seed| {
+            let mut rng = Lcg::new(seed);
+            (0..100).map(|_| rng.below(7)).collect::<Vec<usize>>()
+        };
+        assert_eq!(draws(1), draws(1));
+        assert_ne!(draws(1), draws(2));
+        assert!(draws(1).iter().all(|&draw| draw < 7));
+
+        let mut rng = Lcg::new(3);
+        for _ in 0..100 {
+            let range = rng.range(10, 2..5);
+            assert!(range.start < 10);
+            assert!((2..5).contains(&range.len()));
+        }
+    }
+}
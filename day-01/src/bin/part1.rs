@@ -1,36 +1,19 @@
-use std::fs::File;
-use std::io::prelude::*;
 use std::io::Error;
 
+use day_01::sum_calibrations;
+
 fn main() -> Result<(), Error> {
     let path = "src/day-01.txt";
-    let input_string = open_path(path)?;
 
-    let calibration_value = sum_callibrations(input_string);
+    let calibration_value = sum_calibrations(path, parse_calibration_value)?;
     println!("{}", calibration_value);
 
     Ok(())
 }
 
-fn open_path(path: &str) -> Result<String, Error> {
-    let mut input_file = File::open(path)?;
-    let mut input_string = String::new();
-    input_file.read_to_string(&mut input_string)?;
-    Ok(input_string)
-}
-
-fn sum_callibrations(input: String) -> i32 {
-    let mut sum = 0;
-    for line in input.lines() {
-        let num = parse_calibration_value(line);
-        sum += num;
-    }
-    sum
-}
-
 fn parse_calibration_value(line: &str) -> i32 {
-    let first_digit = line.chars().find(|x| x.is_digit(10)).unwrap();
-    let last_digit = line.chars().rfind(|x| x.is_digit(10)).unwrap();
+    let first_digit = line.chars().find(|x| x.is_ascii_digit()).unwrap();
+    let last_digit = line.chars().rfind(|x| x.is_ascii_digit()).unwrap();
 
     let mut num = String::new();
     num.push(first_digit);
@@ -1,48 +1,109 @@
-use std::fs::File;
-use std::io::prelude::*;
-use std::io::Error;
+use std::io::{Error, ErrorKind};
+use std::str::FromStr;
 
-use aho_corasick::AhoCorasick;
+use aho_corasick::{AhoCorasick, MatchKind};
+use day_01::sum_calibrations;
 
 fn main() -> Result<(), Error> {
     let path = "src/day-01.txt";
-    let input_string = open_path(path)?;
+    let semantics = match std::env::args().nth(1) {
+        Some(arg) => arg
+            .parse::<MatchSemantics>()
+            .map_err(|err| Error::new(ErrorKind::InvalidInput, err))?,
+        None => MatchSemantics::default(),
+    };
+    let calibrator = Calibrator::new(semantics);
 
-    let calibration_value = sum_callibrations(input_string);
+    let calibration_value =
+        sum_calibrations(path, |line| parse_calibration_value(line, &calibrator))?;
     println!("{}", calibration_value);
 
     Ok(())
 }
 
-fn open_path(path: &str) -> Result<String, Error> {
-    let mut input_file = File::open(path)?;
-    let mut input_string = String::new();
-    input_file.read_to_string(&mut input_string)?;
-    Ok(input_string)
+const PATTERNS: &[&str] = &[
+    "1", "2", "3", "4", "5", "6", "7", "8", "9", "one", "two", "three", "four", "five", "six",
+    "seven", "eight", "nine",
+];
+
+/// How digit words that share letters (`"twone"`, `"oneight"`) are resolved.
+///
+/// - `Overlapping` reports every match, so `"twone"` yields both `two` and `one`.
+///   This is what the puzzle expects.
+/// - `LeftmostFirst` scans left to right without overlaps and, when several
+///   patterns start at the same position, prefers the one listed first.
+/// - `LeftmostLongest` scans the same way but prefers the longest pattern.
+///
+/// No digit word is a prefix of another, so the two leftmost modes agree on
+/// every input; they are kept separate so the choice is spelled out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum MatchSemantics {
+    #[default]
+    Overlapping,
+    LeftmostFirst,
+    LeftmostLongest,
+}
+
+impl MatchSemantics {
+    fn match_kind(self) -> MatchKind {
+        match self {
+            MatchSemantics::Overlapping => MatchKind::Standard,
+            MatchSemantics::LeftmostFirst => MatchKind::LeftmostFirst,
+            MatchSemantics::LeftmostLongest => MatchKind::LeftmostLongest,
+        }
+    }
 }
 
-fn sum_callibrations(input: String) -> i32 {
-    let mut sum = 0;
-    for line in input.lines() {
-        let num = parse_calibration_value(line);
-        sum += num;
+impl FromStr for MatchSemantics {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "overlapping" => Ok(MatchSemantics::Overlapping),
+            "leftmost-first" => Ok(MatchSemantics::LeftmostFirst),
+            "leftmost-longest" => Ok(MatchSemantics::LeftmostLongest),
+            _ => Err(format!(
+                "unknown match semantics `{}` (expected overlapping, leftmost-first or leftmost-longest)",
+                s
+            )),
+        }
     }
-    sum
 }
 
-fn parse_calibration_value(line: &str) -> i32 {
-    let patterns = &[
-        "1", "2", "3", "4", "5", "6", "7", "8", "9", "one", "two", "three", "four", "five", "six",
-        "seven", "eight", "nine",
-    ];
+struct Calibrator {
+    ac: AhoCorasick,
+    semantics: MatchSemantics,
+}
+
+impl Calibrator {
+    fn new(semantics: MatchSemantics) -> Self {
+        let ac = AhoCorasick::builder()
+            .match_kind(semantics.match_kind())
+            .build(PATTERNS)
+            .unwrap();
+        Self { ac, semantics }
+    }
+
+    fn find_numbers(&self, line: &str) -> Vec<&'static str> {
+        let matches: Vec<usize> = match self.semantics {
+            MatchSemantics::Overlapping => self
+                .ac
+                .find_overlapping_iter(line)
+                .map(|mat| mat.pattern().as_usize())
+                .collect(),
+            MatchSemantics::LeftmostFirst | MatchSemantics::LeftmostLongest => self
+                .ac
+                .find_iter(line)
+                .map(|mat| mat.pattern().as_usize())
+                .collect(),
+        };
 
-    let ac = AhoCorasick::new(patterns).unwrap();
-    let matches: Vec<usize> = ac
-        .find_overlapping_iter(line)
-        .map(|mat| mat.pattern().as_usize())
-        .collect();
+        matches.into_iter().map(|idx| PATTERNS[idx]).collect()
+    }
+}
 
-    let numbers: Vec<&str> = matches.into_iter().map(|idx| patterns[idx]).collect();
+fn parse_calibration_value(line: &str, calibrator: &Calibrator) -> i32 {
+    let numbers = calibrator.find_numbers(line);
 
     let first_digit = convert_string_number(numbers.first().unwrap());
     let last_digit = convert_string_number(numbers.last().unwrap());
@@ -72,10 +133,59 @@ mod tests {
 
     #[test]
     fn test_parse_calibration_value() {
-        assert_eq!(92, parse_calibration_value("nine92jnhgqzctpgbcbpz"));
-        assert_eq!(72, parse_calibration_value("lkajdsf7klsdftwo"));
-        assert_eq!(32, parse_calibration_value("lkjfew3seventeentwentytwo"));
-        assert_eq!(43, parse_calibration_value("ljflewkfour342353seven3"));
-        assert_eq!(23, parse_calibration_value("twoneeighthree"));
+        let calibrator = Calibrator::new(MatchSemantics::Overlapping);
+        assert_eq!(
+            92,
+            parse_calibration_value("nine92jnhgqzctpgbcbpz", &calibrator)
+        );
+        assert_eq!(72, parse_calibration_value("lkajdsf7klsdftwo", &calibrator));
+        assert_eq!(
+            32,
+            parse_calibration_value("lkjfew3seventeentwentytwo", &calibrator)
+        );
+        assert_eq!(
+            43,
+            parse_calibration_value("ljflewkfour342353seven3", &calibrator)
+        );
+        assert_eq!(23, parse_calibration_value("twoneeighthree", &calibrator));
+    }
+
+    #[test]
+    fn test_overlapping_semantics() {
+        let calibrator = Calibrator::new(MatchSemantics::Overlapping);
+        assert_eq!(calibrator.find_numbers("twone"), vec!["two", "one"]);
+        assert_eq!(21, parse_calibration_value("twone", &calibrator));
+        assert_eq!(18, parse_calibration_value("oneight", &calibrator));
+        assert_eq!(83, parse_calibration_value("eightwothree", &calibrator));
+    }
+
+    #[test]
+    fn test_leftmost_first_semantics() {
+        let calibrator = Calibrator::new(MatchSemantics::LeftmostFirst);
+        assert_eq!(calibrator.find_numbers("twone"), vec!["two"]);
+        assert_eq!(22, parse_calibration_value("twone", &calibrator));
+        assert_eq!(11, parse_calibration_value("oneight", &calibrator));
+        assert_eq!(83, parse_calibration_value("eightwothree", &calibrator));
+        assert_eq!(28, parse_calibration_value("twoneeighthree", &calibrator));
+    }
+
+    #[test]
+    fn test_leftmost_longest_semantics() {
+        let calibrator = Calibrator::new(MatchSemantics::LeftmostLongest);
+        assert_eq!(calibrator.find_numbers("twone"), vec!["two"]);
+        assert_eq!(22, parse_calibration_value("twone", &calibrator));
+        assert_eq!(11, parse_calibration_value("oneight", &calibrator));
+        assert_eq!(83, parse_calibration_value("eightwothree", &calibrator));
+    }
+
+    #[test]
+    fn test_match_semantics_from_str() {
+        assert_eq!("overlapping".parse(), Ok(MatchSemantics::Overlapping));
+        assert_eq!("leftmost-first".parse(), Ok(MatchSemantics::LeftmostFirst));
+        assert_eq!(
+            "leftmost-longest".parse(),
+            Ok(MatchSemantics::LeftmostLongest)
+        );
+        assert!("greedy".parse::<MatchSemantics>().is_err());
     }
 }
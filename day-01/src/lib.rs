@@ -0,0 +1,160 @@
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufReader, Error, SeekFrom};
+use std::path::Path;
+use std::thread;
+
+/// Sums the calibration value of every line in the file at `path`, using one
+/// chunk per available CPU.
+pub fn sum_calibrations<P, F>(path: P, parse: F) -> Result<i64, Error>
+where
+    P: AsRef<Path>,
+    F: Fn(&str) -> i32 + Sync,
+{
+    let chunks = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    sum_calibrations_chunked(path, chunks, parse)
+}
+
+/// Splits the file into `chunks` byte ranges that start and end on line
+/// boundaries and sums each range on its own thread. Lines are streamed
+/// through a buffered reader, so the file is never held in memory as a whole.
+///
+/// Lines are yielded the same way as `str::lines`: the trailing `\n` (and a
+/// `\r` before it) is stripped and a final newline does not produce an empty
+/// line.
+pub fn sum_calibrations_chunked<P, F>(path: P, chunks: usize, parse: F) -> Result<i64, Error>
+where
+    P: AsRef<Path>,
+    F: Fn(&str) -> i32 + Sync,
+{
+    let path = path.as_ref();
+    let boundaries = chunk_boundaries(path, chunks.max(1))?;
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = boundaries
+            .windows(2)
+            .map(|range| {
+                let (start, end) = (range[0], range[1]);
+                let parse = &parse;
+                scope.spawn(move || sum_range(path, start, end, parse))
+            })
+            .collect();
+
+        let mut sum = 0;
+        for handle in handles {
+            sum += handle.join().unwrap()?;
+        }
+        Ok(sum)
+    })
+}
+
+/// Byte offsets `[0, .., len]` where each inner offset is the start of a line.
+fn chunk_boundaries(path: &Path, chunks: usize) -> Result<Vec<u64>, Error> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+
+    let mut boundaries = vec![0];
+    let mut line = Vec::new();
+    for k in 1..chunks as u64 {
+        let target = len * k / chunks as u64;
+        let previous = *boundaries.last().unwrap();
+        if target <= previous {
+            continue;
+        }
+
+        // Start scanning one byte early so a target that already sits at the
+        // start of a line is kept as is.
+        file.seek(SeekFrom::Start(target - 1))?;
+        line.clear();
+        let read = BufReader::new(&mut file).read_until(b'\n', &mut line)?;
+        boundaries.push((target - 1 + read as u64).min(len));
+    }
+    boundaries.push(len);
+    boundaries.dedup();
+    Ok(boundaries)
+}
+
+fn sum_range<F>(path: &Path, start: u64, end: u64, parse: &F) -> Result<i64, Error>
+where
+    F: Fn(&str) -> i32,
+{
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(start))?;
+    let mut reader = BufReader::new(file.take(end - start));
+
+    let mut sum = 0;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        let trimmed = line.strip_suffix('\n').unwrap_or(&line);
+        let trimmed = trimmed.strip_suffix('\r').unwrap_or(trimmed);
+        sum += parse(trimmed) as i64;
+    }
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn write_temp_file(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("day-01-{}-{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn first_and_last_digit(line: &str) -> i32 {
+        let first = line.chars().find(|c| c.is_ascii_digit()).unwrap();
+        let last = line.chars().rfind(|c| c.is_ascii_digit()).unwrap();
+        format!("{}{}", first, last).parse().unwrap()
+    }
+
+    #[test]
+    fn test_sum_calibrations_chunked_matches_lines() {
+        let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n";
+        let path = write_temp_file("example", input);
+
+        for chunks in 1..=input.len() + 2 {
+            assert_eq!(
+                sum_calibrations_chunked(&path, chunks, first_and_last_digit).unwrap(),
+                142
+            );
+        }
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_sum_calibrations_chunked_without_trailing_newline() {
+        let input = "12\r\n34\r\n56";
+        let path = write_temp_file("crlf", input);
+
+        for chunks in 1..=input.len() {
+            assert_eq!(
+                sum_calibrations_chunked(&path, chunks, first_and_last_digit).unwrap(),
+                12 + 34 + 56
+            );
+        }
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_chunk_boundaries_start_lines() {
+        let input = "11\n2222\n3\n44444444\n5\n";
+        let path = write_temp_file("boundaries", input);
+
+        let boundaries = chunk_boundaries(&path, 4).unwrap();
+        assert_eq!(boundaries.first(), Some(&0));
+        assert_eq!(boundaries.last(), Some(&(input.len() as u64)));
+        for &offset in &boundaries[1..boundaries.len() - 1] {
+            assert_eq!(input.as_bytes()[offset as usize - 1], b'\n');
+        }
+        fs::remove_file(path).unwrap();
+    }
+}
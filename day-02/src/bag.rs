@@ -0,0 +1,238 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::{Color, Game};
+
+/// How many cubes of each color the bag holds. The bag also defines which
+/// colors exist: a game showing any other color is rejected by
+/// [`Bag::check_colors`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bag {
+    counts: BTreeMap<Color, usize>,
+}
+
+impl Bag {
+    /// Reads a bag from a file holding `color=count` entries separated by
+    /// commas or newlines. Blank lines and lines starting with `#` are
+    /// ignored.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let mut file = File::open(path)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+
+        let spec: Vec<&str> = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect();
+        spec.join(",")
+            .parse()
+            .map_err(|err| Error::new(ErrorKind::InvalidData, err))
+    }
+
+    pub fn get(&self, color: &Color) -> Option<usize> {
+        self.counts.get(color).copied()
+    }
+
+    pub fn colors(&self) -> impl Iterator<Item = &Color> + '_ {
+        self.counts.keys()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Color, usize)> + '_ {
+        self.counts.iter().map(|(color, &count)| (color, count))
+    }
+
+    /// Whether every round of `game` could have been drawn from this bag.
+    pub fn allows(&self, game: &Game) -> bool {
+        game.rounds.iter().all(|round| {
+            round
+                .iter()
+                .all(|(color, count)| count <= self.get(color).unwrap_or(0))
+        })
+    }
+
+    /// Product of the largest count of each bag color shown in `game`. A bag
+    /// color the game never shows contributes zero.
+    pub fn power(&self, game: &Game) -> usize {
+        self.colors().map(|color| game.max_count(color)).product()
+    }
+
+    /// Fails on the first game showing a color this bag does not know about.
+    pub fn check_colors(&self, games: &[Game]) -> Result<(), BagError> {
+        for game in games {
+            for color in game.colors() {
+                if !self.counts.contains_key(color) {
+                    return Err(BagError::UnknownColor {
+                        game: game.id,
+                        color: color.clone(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The bag from the puzzle: 12 red, 13 green and 14 blue cubes.
+impl Default for Bag {
+    fn default() -> Self {
+        "red=12,green=13,blue=14".parse().unwrap()
+    }
+}
+
+impl FromIterator<(Color, usize)> for Bag {
+    fn from_iter<I: IntoIterator<Item = (Color, usize)>>(iter: I) -> Self {
+        Bag {
+            counts: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Parses `color=count` entries separated by commas, e.g.
+/// `red=12,green=13,blue=14`.
+impl FromStr for Bag {
+    type Err = BagError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut counts = BTreeMap::new();
+        for entry in s.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (color, count) = entry
+                .split_once('=')
+                .ok_or_else(|| BagError::MalformedEntry(entry.to_string()))?;
+            let color = color
+                .trim()
+                .parse::<Color>()
+                .map_err(|_| BagError::MalformedEntry(entry.to_string()))?;
+            let count = count
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| BagError::InvalidCount(count.trim().to_string()))?;
+            if counts.contains_key(&color) {
+                return Err(BagError::DuplicateColor(color));
+            }
+            counts.insert(color, count);
+        }
+        if counts.is_empty() {
+            return Err(BagError::Empty);
+        }
+        Ok(Bag { counts })
+    }
+}
+
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries: Vec<String> = self
+            .iter()
+            .map(|(color, count)| format!("{}={}", color, count))
+            .collect();
+        write!(f, "{}", entries.join(","))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BagError {
+    Empty,
+    MalformedEntry(String),
+    InvalidCount(String),
+    DuplicateColor(Color),
+    UnknownColor { game: usize, color: Color },
+}
+
+impl fmt::Display for BagError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BagError::Empty => write!(f, "bag holds no colors"),
+            BagError::MalformedEntry(entry) => {
+                write!(f, "expected `<color>=<count>`, found `{}`", entry)
+            }
+            BagError::InvalidCount(count) => write!(f, "invalid cube count `{}`", count),
+            BagError::DuplicateColor(color) => write!(f, "color `{}` listed twice", color),
+            BagError::UnknownColor { game, color } => {
+                write!(f, "game {} shows unknown color `{}`", game, color)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BagError {}
+
+impl From<BagError> for Error {
+    fn from(err: BagError) -> Self {
+        Error::new(ErrorKind::InvalidData, err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::tests::EXAMPLE;
+    use crate::parse_games;
+
+    #[test]
+    fn test_parse_bag() {
+        let bag = "red=12, green=13,blue=14".parse::<Bag>().unwrap();
+        assert_eq!(bag, Bag::default());
+        assert_eq!(bag.get(&"green".parse().unwrap()), Some(13));
+        assert_eq!(bag.to_string(), "blue=14,green=13,red=12");
+
+        assert_eq!("".parse::<Bag>(), Err(BagError::Empty));
+        assert_eq!(
+            "red:12".parse::<Bag>(),
+            Err(BagError::MalformedEntry("red:12".to_string()))
+        );
+        assert_eq!(
+            "red=many".parse::<Bag>(),
+            Err(BagError::InvalidCount("many".to_string()))
+        );
+        assert_eq!(
+            "red=1,red=2".parse::<Bag>(),
+            Err(BagError::DuplicateColor("red".parse().unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_default_bag() {
+        let games = parse_games(EXAMPLE).unwrap();
+        let bag = Bag::default();
+
+        let possible: usize = games
+            .iter()
+            .filter(|game| bag.allows(game))
+            .map(|game| game.id)
+            .sum();
+        assert_eq!(possible, 8);
+
+        let power: usize = games.iter().map(|game| bag.power(game)).sum();
+        assert_eq!(power, 2286);
+    }
+
+    #[test]
+    fn test_custom_colors() {
+        let games = parse_games(
+            "Game 1: 2 yellow, 1 red; 3 yellow
+Game 2: 5 yellow; 1 red, 1 violet",
+        )
+        .unwrap();
+        let bag = "red=1,yellow=4,violet=2".parse::<Bag>().unwrap();
+
+        assert!(bag.allows(&games[0]));
+        assert!(!bag.allows(&games[1]));
+        assert_eq!(bag.power(&games[0]), 0);
+        assert_eq!(bag.power(&games[1]), 5);
+        assert!(bag.check_colors(&games).is_ok());
+
+        let bag = Bag::default();
+        assert_eq!(
+            bag.check_colors(&games),
+            Err(BagError::UnknownColor {
+                game: 1,
+                color: "yellow".parse().unwrap()
+            })
+        );
+    }
+}
@@ -1,56 +1,51 @@
-use std::fs::File;
-use std::io::prelude::*;
-use std::io::Error;
+use std::env;
+use std::io::{Error, ErrorKind};
 
-fn main() -> Result<(), Error> {
-    let path = "src/day-02.txt";
-    let input = open_file(path)?;
-
-    let mut sum = 0;
-    for line in input.lines() {
-        let num = parse_input(line);
-        sum += num;
-    }
-    println!("{}", sum);
-    Ok(())
-}
-
-fn open_file(path: &str) -> Result<String, Error> {
-    let mut file = File::open(path)?;
-    let mut input = String::new();
-    file.read_to_string(&mut input)?;
-    Ok(input)
-}
+use day_02::{parse_games, Bag, Report};
 
-fn parse_input(input: &str) -> usize {
-    const RED_MAX: usize = 12;
-    const GREEN_MAX: usize = 13;
-    const BLUE_MAX: usize = 14;
-    let input: Vec<&str> = input.split(":").collect();
-    let game_id = input.first().unwrap().split_whitespace().last().unwrap();
+/// Usage: `part1 [--bag red=12,green=13,blue=14 | --bag-file <path>]
+///               [--report text|json] [input]`
+///
+/// `--report` prints why each game is or isn't possible instead of the id sum.
+fn main() -> Result<(), Error> {
+    let mut bag = Bag::default();
+    let mut report = None;
 
-    let sets: Vec<&str> = input.last().unwrap().split(";").collect();
-    let mut games = Vec::new();
-    for set in &sets {
-        let mut game: Vec<&str> = set.split(",").collect();
-        games.append(&mut game);
-    }
+    let positional = aoc_cli::parse(env::args().skip(1), |values| {
+        match values.flag() {
+            "--bag" => bag = values.parse()?,
+            "--bag-file" => bag = Bag::from_file(values.value()?)?,
+            "--report" => report = Some(values.value()?),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    let path = aoc_cli::input_path(positional, "src/day-02.txt")?;
 
-    for color_pair in games {
-        let color_pair = color_pair.trim();
-        let pair: Vec<&str> = color_pair.split_whitespace().collect();
-        let qty = pair.first().unwrap();
-        let color = pair.last().unwrap();
+    let input = aoc_cli::read_input(&path)?;
+    let games = parse_games(&input)?;
+    bag.check_colors(&games)?;
 
-        if color == &"blue" && qty.parse::<usize>().unwrap() > BLUE_MAX {
-            return 0;
-        }
-        if color == &"red" && qty.parse::<usize>().unwrap() > RED_MAX {
-            return 0;
-        }
-        if color == &"green" && qty.parse::<usize>().unwrap() > GREEN_MAX {
-            return 0;
+    if let Some(format) = report {
+        let report = Report::new(&bag, &games);
+        match format.as_str() {
+            "text" => print!("{}", report),
+            "json" => println!("{}", report.to_json()),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("unknown report format `{}`", format),
+                ))
+            }
         }
+        return Ok(());
     }
-    game_id.parse::<usize>().unwrap()
+
+    let sum: usize = games
+        .iter()
+        .filter(|game| bag.allows(game))
+        .map(|game| game.id)
+        .sum();
+    println!("{}", sum);
+    Ok(())
 }
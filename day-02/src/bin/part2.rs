@@ -1,69 +1,51 @@
-use std::fs::File;
-use std::io::prelude::*;
-use std::io::Error;
-
-// const RED_MAX: usize = 12;
-// const GREEN_MAX: usize = 13;
-// const BLUE_MAX: usize = 14;
-
+use std::env;
+use std::io::{Error, ErrorKind};
+
+use day_02::{infer_bag, parse_games, Bag};
+
+/// Usage: `part2 [--bag red=12,green=13,blue=14 | --bag-file <path>]
+///               [--infer [--max-cubes <n>]] [input]`
+///
+/// `--infer` estimates the most likely bag behind the games instead of
+/// summing their powers. Only the colors of the bag are used, not its counts.
+/// Games whose likelihood keeps rising with the bag have no estimate, which
+/// is an error rather than an answer pinned to `--max-cubes`.
 fn main() -> Result<(), Error> {
-    let path = "src/day-02.txt";
-    let input = open_file(path)?;
-
-    let mut sum = 0;
-    for line in input.lines() {
-        let num = parse_input(line);
-        sum += num;
-    }
-    println!("{}", sum);
-    Ok(())
-}
-
-fn open_file(path: &str) -> Result<String, Error> {
-    let mut file = File::open(path)?;
-    let mut input = String::new();
-    file.read_to_string(&mut input)?;
-    Ok(input)
-}
-
-fn parse_input(input: &str) -> usize {
-    let input: Vec<&str> = input.split(":").collect();
-
-    let sets: Vec<&str> = input.last().unwrap().split(";").collect();
-    let mut games = Vec::new();
-    for set in &sets {
-        let mut game: Vec<&str> = set.split(",").collect();
-        games.append(&mut game);
-    }
-
-    let mut max_red: usize = 0;
-    let mut max_blue: usize = 0;
-    let mut max_green: usize = 0;
-    for color_pair in games {
-        let color_pair = color_pair.trim();
-        let pair: Vec<&str> = color_pair.split_whitespace().collect();
-        let qty = pair.first().unwrap().parse::<usize>().unwrap();
-        let color = pair.last().unwrap();
-        match (color, qty) {
-            (&"blue", qty) => {
-                if qty > max_blue {
-                    max_blue = qty
-                }
-            }
-            (&"red", qty) => {
-                if qty > max_red {
-                    max_red = qty
-                }
-            }
-            (&"green", qty) => {
-                if qty > max_green {
-                    max_green = qty
-                }
-            }
-            _ => (),
+    let mut bag = Bag::default();
+    let mut infer = false;
+    let mut max_cubes = 100;
+
+    let positional = aoc_cli::parse(env::args().skip(1), |values| {
+        match values.flag() {
+            "--bag" => bag = values.parse()?,
+            "--bag-file" => bag = Bag::from_file(values.value()?)?,
+            "--infer" => infer = true,
+            "--max-cubes" => max_cubes = values.parse()?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    let path = aoc_cli::input_path(positional, "src/day-02.txt")?;
+
+    let input = aoc_cli::read_input(&path)?;
+    let games = parse_games(&input)?;
+    bag.check_colors(&games)?;
+
+    if infer {
+        let inference = infer_bag(&games, bag.colors(), max_cubes);
+        if inference.limited {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "likelihood unbounded; estimate limited by --max-cubes",
+            ));
         }
+        println!("bag: {}", inference.bag);
+        println!("log-likelihood: {:.3}", inference.log_likelihood);
+        println!("confidence: {:.3}", inference.confidence);
+        return Ok(());
     }
 
-    let power = max_red * max_blue * max_green;
-    power
+    let sum: usize = games.iter().map(|game| bag.power(game)).sum();
+    println!("{}", sum);
+    Ok(())
 }
@@ -0,0 +1,37 @@
+use std::env;
+use std::io::{Error, ErrorKind};
+
+use day_02::{parse_games, Bag, Query};
+
+/// Usage: `query [--bag red=12,green=13,blue=14 | --bag-file <path>] <query> [input]`
+///
+/// e.g. `query 'sum(id) where rounds <= 3'`. See the `query` module for the
+/// full syntax.
+fn main() -> Result<(), Error> {
+    let mut bag = Bag::default();
+
+    let mut positional = aoc_cli::parse(env::args().skip(1), |values| {
+        match values.flag() {
+            "--bag" => bag = values.parse()?,
+            "--bag-file" => bag = Bag::from_file(values.value()?)?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    if positional.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "missing query argument",
+        ));
+    }
+    let query = positional.remove(0).parse::<Query>()?;
+    query.check_colors(&bag)?;
+    let path = aoc_cli::input_path(positional, "src/day-02.txt")?;
+
+    let input = aoc_cli::read_input(&path)?;
+    let games = parse_games(&input)?;
+    bag.check_colors(&games)?;
+
+    println!("{}", query.evaluate(&games, &bag));
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use std::env;
+use std::io::Error;
+use std::time::Duration;
+
+use day_02::{stream_games, Bag, Game, Totals};
+
+/// Usage: `stream [--bag red=12,green=13,blue=14 | --bag-file <path>] [--follow] [input]`
+///
+/// Reads games from `input` (stdin when missing or `-`) and prints the
+/// running part 1 and part 2 totals after each one. `--follow` keeps waiting
+/// for lines appended to the file instead of stopping at its end; stdin is
+/// always read until it closes, since nothing can be appended after that.
+fn main() -> Result<(), Error> {
+    let mut bag = Bag::default();
+    let mut poll = None;
+
+    let positional = aoc_cli::parse(env::args().skip(1), |values| {
+        match values.flag() {
+            "--bag" => bag = values.parse()?,
+            "--bag-file" => bag = Bag::from_file(values.value()?)?,
+            "--follow" => poll = Some(Duration::from_millis(250)),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    let path = aoc_cli::input_path(positional, "-")?;
+
+    let print = |game: &Game, totals: Totals| {
+        println!(
+            "game {}: part1 {} part2 {}",
+            game.id, totals.id_sum, totals.power_sum
+        );
+        Ok(())
+    };
+    let poll = if path == "-" { None } else { poll };
+    stream_games(aoc_cli::open_reader(&path)?, bag, poll, print)?;
+    Ok(())
+}
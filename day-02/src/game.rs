@@ -0,0 +1,286 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::io::{Error, ErrorKind};
+use std::str::FromStr;
+
+/// A cube color, e.g. `red`. Any lowercase word is accepted here; which
+/// colors actually exist is decided by the [`Bag`](crate::Bag).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Color(String);
+
+impl Color {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseGameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || !s.chars().all(|c| c.is_ascii_lowercase()) {
+            return Err(ParseGameError::InvalidColor(s.to_string()));
+        }
+        Ok(Color(s.to_string()))
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A single `<count> <color>` entry of a round, e.g. `3 blue`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Draw {
+    pub count: usize,
+    pub color: Color,
+}
+
+impl FromStr for Draw {
+    type Err = ParseGameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let (Some(count), Some(color), None) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(ParseGameError::MalformedDraw(s.trim().to_string()));
+        };
+        let count = count
+            .parse::<usize>()
+            .map_err(|_| ParseGameError::InvalidCount(count.to_string()))?;
+        let color = color.parse::<Color>()?;
+        Ok(Draw { count, color })
+    }
+}
+
+/// The cubes shown in one round of a game. Colors that were not drawn count
+/// as zero.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Round {
+    counts: BTreeMap<Color, usize>,
+}
+
+impl Round {
+    pub fn get(&self, color: &Color) -> usize {
+        self.counts.get(color).copied().unwrap_or(0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Color, usize)> + '_ {
+        self.counts.iter().map(|(color, &count)| (color, count))
+    }
+
+    pub fn colors(&self) -> impl Iterator<Item = &Color> + '_ {
+        self.counts.keys()
+    }
+}
+
+impl FromIterator<Draw> for Round {
+    fn from_iter<I: IntoIterator<Item = Draw>>(iter: I) -> Self {
+        let mut round = Round::default();
+        for draw in iter {
+            *round.counts.entry(draw.color).or_default() += draw.count;
+        }
+        round
+    }
+}
+
+impl FromStr for Round {
+    type Err = ParseGameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut round = Round::default();
+        for draw in s.split(',') {
+            let draw = draw.parse::<Draw>()?;
+            if round.counts.contains_key(&draw.color) {
+                return Err(ParseGameError::DuplicateColor(draw.color));
+            }
+            round.counts.insert(draw.color, draw.count);
+        }
+        Ok(round)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: usize,
+    pub rounds: Vec<Round>,
+}
+
+impl Game {
+    /// Largest count of `color` shown in any round.
+    pub fn max_count(&self, color: &Color) -> usize {
+        self.rounds
+            .iter()
+            .map(|round| round.get(color))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Every color shown in at least one round.
+    pub fn colors(&self) -> BTreeSet<&Color> {
+        self.rounds.iter().flat_map(Round::colors).collect()
+    }
+
+    /// The fewest cubes of each shown color the bag could have held for this
+    /// game to be possible.
+    pub fn minimum_set(&self) -> Round {
+        self.colors()
+            .into_iter()
+            .map(|color| Draw {
+                count: self.max_count(color),
+                color: color.clone(),
+            })
+            .collect()
+    }
+}
+
+impl FromStr for Game {
+    type Err = ParseGameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (header, rounds) = s
+            .split_once(':')
+            .ok_or_else(|| ParseGameError::MissingColon(s.to_string()))?;
+
+        let id = match header.split_whitespace().collect::<Vec<&str>>()[..] {
+            ["Game", id] => id
+                .parse::<usize>()
+                .map_err(|_| ParseGameError::InvalidId(id.to_string()))?,
+            _ => return Err(ParseGameError::MalformedHeader(header.to_string())),
+        };
+
+        let rounds = rounds
+            .split(';')
+            .map(|round| round.parse::<Round>())
+            .collect::<Result<Vec<Round>, ParseGameError>>()?;
+
+        Ok(Game { id, rounds })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGameError {
+    MissingColon(String),
+    MalformedHeader(String),
+    InvalidId(String),
+    MalformedDraw(String),
+    InvalidCount(String),
+    InvalidColor(String),
+    DuplicateColor(Color),
+}
+
+impl fmt::Display for ParseGameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGameError::MissingColon(line) => write!(f, "missing `:` in `{}`", line),
+            ParseGameError::MalformedHeader(header) => {
+                write!(f, "expected `Game <id>`, found `{}`", header)
+            }
+            ParseGameError::InvalidId(id) => write!(f, "invalid game id `{}`", id),
+            ParseGameError::MalformedDraw(draw) => {
+                write!(f, "expected `<count> <color>`, found `{}`", draw)
+            }
+            ParseGameError::InvalidCount(count) => write!(f, "invalid cube count `{}`", count),
+            ParseGameError::InvalidColor(color) => write!(f, "invalid color `{}`", color),
+            ParseGameError::DuplicateColor(color) => {
+                write!(f, "color `{}` drawn twice in one round", color)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseGameError {}
+
+/// Parses one game per non-empty line, reporting the first bad line.
+pub fn parse_games(input: &str) -> Result<Vec<Game>, Error> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(row, line)| {
+            line.parse::<Game>().map_err(|err| {
+                Error::new(ErrorKind::InvalidData, format!("line {}: {}", row + 1, err))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    fn color(name: &str) -> Color {
+        name.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_game() {
+        let game = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
+            .parse::<Game>()
+            .unwrap();
+        assert_eq!(game.id, 1);
+        assert_eq!(game.rounds.len(), 3);
+        assert_eq!(game.rounds[0].get(&color("blue")), 3);
+        assert_eq!(game.rounds[0].get(&color("red")), 4);
+        assert_eq!(game.rounds[0].get(&color("green")), 0);
+        assert_eq!(game.rounds[1].get(&color("blue")), 6);
+        assert_eq!(game.rounds[2].get(&color("green")), 2);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "Game 1: 3 Purple".parse::<Game>(),
+            Err(ParseGameError::InvalidColor("Purple".to_string()))
+        );
+        assert_eq!(
+            "Game 1: three blue".parse::<Game>(),
+            Err(ParseGameError::InvalidCount("three".to_string()))
+        );
+        assert_eq!(
+            "Game 1: 3 blue, 4 blue".parse::<Game>(),
+            Err(ParseGameError::DuplicateColor(color("blue")))
+        );
+        assert_eq!(
+            "Game 1: 3 blue red".parse::<Game>(),
+            Err(ParseGameError::MalformedDraw("3 blue red".to_string()))
+        );
+        assert_eq!(
+            "Game x: 3 blue".parse::<Game>(),
+            Err(ParseGameError::InvalidId("x".to_string()))
+        );
+        assert_eq!(
+            "Round 1: 3 blue".parse::<Game>(),
+            Err(ParseGameError::MalformedHeader("Round 1".to_string()))
+        );
+        assert!("Game 1 3 blue".parse::<Game>().is_err());
+    }
+
+    #[test]
+    fn test_parse_games_reports_line() {
+        let err = parse_games("Game 1: 1 red\nGame 2: 1 Pink").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: invalid color `Pink`");
+    }
+
+    #[test]
+    fn test_minimum_set() {
+        let games = parse_games(EXAMPLE).unwrap();
+        let minimum = games[2].minimum_set();
+        assert_eq!(minimum.get(&color("red")), 20);
+        assert_eq!(minimum.get(&color("green")), 13);
+        assert_eq!(minimum.get(&color("blue")), 6);
+
+        let game = "Game 9: 2 yellow; 5 yellow, 1 red".parse::<Game>().unwrap();
+        let minimum = game.minimum_set();
+        assert_eq!(minimum.colors().count(), 2);
+        assert_eq!(minimum.get(&color("yellow")), 5);
+        assert_eq!(minimum.get(&color("blue")), 0);
+    }
+}
@@ -0,0 +1,296 @@
+use crate::{Bag, Color, Game, Round};
+
+/// The most likely bag found by [`infer_bag`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inference {
+    pub bag: Bag,
+    /// Natural log of the probability of every observed round given `bag`.
+    pub log_likelihood: f64,
+    /// Share of the likelihood held by `bag` among itself and every bag one
+    /// cube away from it, in `(0, 1]`. Close to 1 means the neighbors explain
+    /// the games much worse; close to `1 / (2 * colors + 1)` means the
+    /// observations barely tell them apart.
+    pub confidence: f64,
+    /// The search ran into `max_per_color` rather than a peak: the likelihood
+    /// was still rising with the bag, so `bag` reflects the limit instead of
+    /// the games.
+    pub limited: bool,
+}
+
+/// Estimates the bag that most plausibly produced every round of `games`.
+///
+/// Each round is modelled as one handful drawn without replacement from the
+/// same bag, with the cubes put back between rounds. The chance of a round
+/// is then multivariate hypergeometric:
+///
+/// ```text
+/// P(round | bag) = prod_c C(n_c, k_c) / C(N, K)
+/// ```
+///
+/// where `n_c` is the number of cubes of color `c` in the bag, `k_c` the
+/// number shown in the round, and `N`, `K` their totals. Colors in `colors`
+/// that a round does not mention count as `k_c = 0`.
+///
+/// Counts are searched between the minimum bag (the largest count shown for
+/// each color) and `max_per_color`, by a pattern search that tries moving one
+/// color at a time, or the whole bag in proportion, by a shrinking step. The likelihood can keep creeping up
+/// as the bag grows without ever peaking; [`Inference::limited`] tells when
+/// the answer ran into `max_per_color` instead of a maximum. No count above
+/// `u32::MAX` is ever tried.
+pub fn infer_bag<'a, C>(games: &[Game], colors: C, max_per_color: usize) -> Inference
+where
+    C: IntoIterator<Item = &'a Color>,
+{
+    let colors: Vec<Color> = colors.into_iter().cloned().collect();
+    let rounds: Vec<Vec<usize>> = games
+        .iter()
+        .flat_map(|game| &game.rounds)
+        .map(|round| colors.iter().map(|color| round.get(color)).collect())
+        .collect();
+
+    let minimum: Vec<usize> = (0..colors.len())
+        .map(|c| rounds.iter().map(|round| round[c]).max().unwrap_or(0))
+        .collect();
+    let max_per_color = max_per_color.min(u32::MAX as usize);
+    let maximum: Vec<usize> = minimum.iter().map(|&n| n.max(max_per_color)).collect();
+    let score = |counts: &[usize]| log_likelihood(counts, &rounds);
+
+    let mut best = minimum.clone();
+    let mut best_score = score(&best);
+    let mut step = maximum
+        .iter()
+        .zip(&minimum)
+        .map(|(max, min)| max - min)
+        .max()
+        .unwrap_or(0)
+        .div_ceil(2)
+        .max(1);
+    loop {
+        let mut improved = false;
+        for c in 0..colors.len() {
+            for candidate in [best[c].checked_sub(step), best[c].checked_add(step)] {
+                let Some(candidate) = candidate else {
+                    continue;
+                };
+                if candidate < minimum[c] || candidate > maximum[c] {
+                    continue;
+                }
+                let mut counts = best.clone();
+                counts[c] = candidate;
+                let candidate_score = score(&counts);
+                if candidate_score > best_score + f64::EPSILON {
+                    best = counts;
+                    best_score = candidate_score;
+                    improved = true;
+                }
+            }
+        }
+
+        // Once the mix of colors is about right the likelihood rises along
+        // a narrow ridge, which single colors only climb a cube at a time;
+        // growing or shrinking the whole bag in proportion follows it.
+        let total: usize = best.iter().sum();
+        for target in [total.checked_sub(step), total.checked_add(step)] {
+            let Some(target) = target.filter(|_| total > 0) else {
+                continue;
+            };
+            let scale = target as f64 / total as f64;
+            let counts: Vec<usize> = best
+                .iter()
+                .zip(minimum.iter().zip(&maximum))
+                .map(|(&n, (&min, &max))| ((n as f64 * scale).round() as usize).clamp(min, max))
+                .collect();
+            let candidate_score = score(&counts);
+            if candidate_score > best_score + f64::EPSILON {
+                best = counts;
+                best_score = candidate_score;
+                improved = true;
+            }
+        }
+
+        if !improved {
+            if step == 1 {
+                break;
+            }
+            step /= 2;
+        }
+    }
+
+    let mut neighborhood = vec![0.0];
+    for c in 0..colors.len() {
+        for candidate in [best[c].checked_sub(1), best[c].checked_add(1)] {
+            let Some(candidate) = candidate else {
+                continue;
+            };
+            if candidate < minimum[c] || candidate > maximum[c] {
+                continue;
+            }
+            let mut counts = best.clone();
+            counts[c] = candidate;
+            neighborhood.push(score(&counts) - best_score);
+        }
+    }
+    let confidence = 1.0 / neighborhood.iter().map(|diff| diff.exp()).sum::<f64>();
+
+    // A likelihood that really peaks does measurably worse once the bag is
+    // scaled up until some color reaches its limit; one still rising only
+    // stopped because of that limit, even if rounding left it a little
+    // short of it.
+    let room = best
+        .iter()
+        .zip(&maximum)
+        .filter(|(&n, _)| n > 0)
+        .map(|(&n, &max)| max as f64 / n as f64)
+        .fold(f64::INFINITY, f64::min);
+    let grown: Vec<usize> = best
+        .iter()
+        .zip(&maximum)
+        .map(|(&n, &max)| ((n as f64 * room).round() as usize).min(max))
+        .collect();
+    let limited = best.iter().zip(&maximum).any(|(n, max)| n == max)
+        || (room.is_finite() && score(&grown) >= best_score - 1e-9);
+
+    Inference {
+        bag: colors.into_iter().zip(best).collect(),
+        log_likelihood: best_score,
+        confidence,
+        limited,
+    }
+}
+
+/// Natural log of `P(round | bag)` for a single round, `-inf` if the round
+/// could not have been drawn from `bag`.
+pub fn round_log_likelihood(bag: &Bag, round: &Round) -> f64 {
+    let colors: Vec<&Color> = bag.colors().collect();
+    if round.colors().any(|color| !colors.contains(&color)) {
+        return f64::NEG_INFINITY;
+    }
+    let counts: Vec<usize> = bag.iter().map(|(_, count)| count).collect();
+    let drawn: Vec<usize> = colors.iter().map(|color| round.get(color)).collect();
+    log_likelihood(&counts, &[drawn])
+}
+
+fn log_likelihood(counts: &[usize], rounds: &[Vec<usize>]) -> f64 {
+    let total: usize = counts.iter().sum();
+    rounds
+        .iter()
+        .map(|round| {
+            let drawn: usize = round.iter().sum();
+            let ways: f64 = counts
+                .iter()
+                .zip(round)
+                .map(|(&n, &k)| ln_choose(n, k))
+                .sum();
+            ways - ln_choose(total, drawn)
+        })
+        .sum()
+}
+
+/// `ln C(n, k)`, `-inf` if `k > n`.
+///
+/// Sums the logs of the factors on the shorter side instead of subtracting
+/// log factorials, so the cost follows the handful rather than the bag and a
+/// huge `n` loses no precision.
+fn ln_choose(n: usize, k: usize) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+    (0..k.min(n - k))
+        .map(|i| ((n - i) as f64 / (i + 1) as f64).ln())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::tests::EXAMPLE;
+    use crate::parse_games;
+    use aoc_testing::Lcg;
+
+    #[test]
+    fn test_round_log_likelihood() {
+        let bag: Bag = "red=2,blue=1".parse().unwrap();
+        let round: Round = "1 red, 1 blue".parse().unwrap();
+        let p = round_log_likelihood(&bag, &round).exp();
+        assert!((p - 2.0 / 3.0).abs() < 1e-9);
+
+        let round: Round = "2 blue".parse().unwrap();
+        assert_eq!(round_log_likelihood(&bag, &round), f64::NEG_INFINITY);
+        let round: Round = "1 green".parse().unwrap();
+        assert_eq!(round_log_likelihood(&bag, &round), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_unseen_color_is_empty() {
+        let games = parse_games("Game 1: 2 red; 1 red\nGame 2: 3 red").unwrap();
+        let colors: Vec<Color> = vec!["red".parse().unwrap(), "blue".parse().unwrap()];
+        let inference = infer_bag(&games, &colors, 20);
+
+        assert_eq!(inference.bag.get(&colors[1]), Some(0));
+        assert!(inference.bag.get(&colors[0]).unwrap() >= 3);
+    }
+
+    #[test]
+    fn test_recovers_sampled_bag() {
+        // Deal 400 handfuls of 4 from a bag of 5 red, 3 green and 2 blue.
+        let bag = ["red"; 5]
+            .iter()
+            .chain(&["green"; 3])
+            .chain(&["blue"; 2])
+            .copied()
+            .collect::<Vec<&str>>();
+        let mut rng = Lcg::new(0x2023);
+        let mut input = String::new();
+        for id in 1..=400 {
+            let mut cubes = bag.clone();
+            let mut handful = Vec::new();
+            for _ in 0..4 {
+                let pick = rng.below(cubes.len());
+                handful.push(cubes.swap_remove(pick));
+            }
+            let round: Vec<String> = ["red", "green", "blue"]
+                .iter()
+                .map(|color| {
+                    let count = handful.iter().filter(|c| *c == color).count();
+                    format!("{} {}", count, color)
+                })
+                .collect();
+            input.push_str(&format!("Game {}: {}\n", id, round.join(", ")));
+        }
+
+        let games = parse_games(&input).unwrap();
+        let colors = Bag::default();
+        let inference = infer_bag(&games, colors.colors(), 30);
+
+        assert_eq!(inference.bag.to_string(), "blue=2,green=3,red=5");
+        assert!(inference.confidence > 0.5);
+        assert!(inference.confidence <= 1.0);
+        assert!(!inference.limited);
+    }
+
+    #[test]
+    fn test_unbounded_likelihood_is_limited() {
+        // The example's likelihood keeps rising with the bag, so every
+        // search ends at the limit however large it is.
+        let games = parse_games(EXAMPLE).unwrap();
+        let colors = Bag::default();
+        for max in [30, 1000, 1 << 40, usize::MAX] {
+            let inference = infer_bag(&games, colors.colors(), max);
+            assert!(inference.limited, "max {}", max);
+        }
+    }
+
+    #[test]
+    fn test_ln_choose() {
+        assert_eq!(ln_choose(7, 0), 0.0);
+        assert_eq!(ln_choose(7, 7), 0.0);
+        assert_eq!(ln_choose(3, 4), f64::NEG_INFINITY);
+        assert!((ln_choose(52, 5) - 2598960f64.ln()).abs() < 1e-12);
+        assert!((ln_choose(52, 47) - 2598960f64.ln()).abs() < 1e-12);
+
+        // C(n, 2) = n (n - 1) / 2, with nothing lost to cancellation.
+        let n = 1usize << 40;
+        let expected = (n as f64).ln() + ((n - 1) as f64).ln() - 2f64.ln();
+        assert!((ln_choose(n, 2) - expected).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,13 @@
+mod bag;
+mod game;
+mod infer;
+mod query;
+mod report;
+mod stream;
+
+pub use bag::{Bag, BagError};
+pub use game::{parse_games, Color, Draw, Game, ParseGameError, Round};
+pub use infer::{infer_bag, round_log_likelihood, Inference};
+pub use query::{Query, QueryError};
+pub use report::{GameReport, Report, Violation};
+pub use stream::{stream_games, tail_lines, Tally, Totals};
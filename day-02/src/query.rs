@@ -0,0 +1,444 @@
+//! A small query language over parsed games.
+//!
+//! ```text
+//! query      := aggregate [ "where" predicate ]
+//! aggregate  := "count" | ( "sum" | "min" | "max" ) "(" term ")"
+//! predicate  := conjunct { "or" conjunct }
+//! conjunct   := negation { "and" negation }
+//! negation   := "not" negation | "(" predicate ")" | comparison
+//! comparison := term ( "<" | "<=" | ">" | ">=" | "==" | "!=" ) number
+//!             | term "in" number ".." number
+//! term       := "id" | "rounds" | "power" | <color>
+//! ```
+//!
+//! A color term is the largest count of that color shown in any round of the
+//! game, so `blue > 10` reads as "blue ever exceeded 10". `power` is the
+//! product of those counts over the bag's colors, as in part 2. Ranges are
+//! inclusive on both ends. `min` and `max` of no games are 0.
+//!
+//! ```text
+//! count where blue > 10
+//! sum(id) where rounds <= 3
+//! sum(power) where id in 1..50 and not (red > 12 or green > 13)
+//! ```
+
+use std::fmt;
+use std::io::{Error, ErrorKind};
+use std::str::FromStr;
+
+use crate::{Bag, Color, Game};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    aggregate: Aggregate,
+    filter: Option<Predicate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Aggregate {
+    Count,
+    Sum(Term),
+    Min(Term),
+    Max(Term),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Term {
+    Id,
+    Rounds,
+    Power,
+    Color(Color),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Predicate {
+    Compare(Term, Op, usize),
+    Between(Term, usize, usize),
+    Not(Box<Predicate>),
+    And(Box<Predicate>, Box<Predicate>),
+    Or(Box<Predicate>, Box<Predicate>),
+}
+
+impl Query {
+    /// Every color the query mentions must be in `bag`.
+    pub fn check_colors(&self, bag: &Bag) -> Result<(), QueryError> {
+        let mut terms = Vec::new();
+        match &self.aggregate {
+            Aggregate::Count => (),
+            Aggregate::Sum(term) | Aggregate::Min(term) | Aggregate::Max(term) => terms.push(term),
+        }
+        if let Some(filter) = &self.filter {
+            filter.terms(&mut terms);
+        }
+
+        for term in terms {
+            if let Term::Color(color) = term {
+                if bag.get(color).is_none() {
+                    return Err(QueryError(format!("unknown color `{}`", color)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn matches(&self, game: &Game, bag: &Bag) -> bool {
+        match &self.filter {
+            Some(filter) => filter.eval(game, bag),
+            None => true,
+        }
+    }
+
+    pub fn evaluate(&self, games: &[Game], bag: &Bag) -> usize {
+        let values = games.iter().filter(|game| self.matches(game, bag));
+        match &self.aggregate {
+            Aggregate::Count => values.count(),
+            Aggregate::Sum(term) => values.map(|game| term.eval(game, bag)).sum(),
+            Aggregate::Min(term) => values.map(|game| term.eval(game, bag)).min().unwrap_or(0),
+            Aggregate::Max(term) => values.map(|game| term.eval(game, bag)).max().unwrap_or(0),
+        }
+    }
+}
+
+impl Term {
+    fn eval(&self, game: &Game, bag: &Bag) -> usize {
+        match self {
+            Term::Id => game.id,
+            Term::Rounds => game.rounds.len(),
+            Term::Power => bag.power(game),
+            Term::Color(color) => game.max_count(color),
+        }
+    }
+}
+
+impl Predicate {
+    fn eval(&self, game: &Game, bag: &Bag) -> bool {
+        match self {
+            Predicate::Compare(term, op, value) => {
+                let term = term.eval(game, bag);
+                match op {
+                    Op::Lt => term < *value,
+                    Op::Le => term <= *value,
+                    Op::Gt => term > *value,
+                    Op::Ge => term >= *value,
+                    Op::Eq => term == *value,
+                    Op::Ne => term != *value,
+                }
+            }
+            Predicate::Between(term, low, high) => (*low..=*high).contains(&term.eval(game, bag)),
+            Predicate::Not(inner) => !inner.eval(game, bag),
+            Predicate::And(left, right) => left.eval(game, bag) && right.eval(game, bag),
+            Predicate::Or(left, right) => left.eval(game, bag) || right.eval(game, bag),
+        }
+    }
+
+    fn terms<'a>(&'a self, terms: &mut Vec<&'a Term>) {
+        match self {
+            Predicate::Compare(term, _, _) | Predicate::Between(term, _, _) => terms.push(term),
+            Predicate::Not(inner) => inner.terms(terms),
+            Predicate::And(left, right) | Predicate::Or(left, right) => {
+                left.terms(terms);
+                right.terms(terms);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Number(usize),
+    Symbol(&'static str),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "{}", word),
+            Token::Number(number) => write!(f, "{}", number),
+            Token::Symbol(symbol) => write!(f, "{}", symbol),
+        }
+    }
+}
+
+const SYMBOLS: [&str; 9] = ["<=", ">=", "==", "!=", "..", "<", ">", "(", ")"];
+
+fn tokenize(input: &str) -> Result<Vec<Token>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut rest = input.trim_start();
+    while let Some(c) = rest.chars().next() {
+        let len = if c.is_ascii_digit() {
+            let len = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let number = rest[..len]
+                .parse()
+                .map_err(|_| QueryError(format!("number `{}` is too large", &rest[..len])))?;
+            tokens.push(Token::Number(number));
+            len
+        } else if c.is_ascii_lowercase() {
+            let len = rest
+                .find(|c: char| !c.is_ascii_lowercase())
+                .unwrap_or(rest.len());
+            tokens.push(Token::Word(rest[..len].to_string()));
+            len
+        } else if let Some(symbol) = SYMBOLS.iter().find(|symbol| rest.starts_with(**symbol)) {
+            tokens.push(Token::Symbol(symbol));
+            symbol.len()
+        } else {
+            return Err(QueryError(format!("unexpected character `{}`", c)));
+        };
+        rest = rest[len..].trim_start();
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Result<Token, QueryError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or_else(|| QueryError("unexpected end of query".to_string()))?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        if self.peek() == Some(&Token::Word(word.to_string())) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<(), QueryError> {
+        match self.next()? {
+            Token::Symbol(found) if found == symbol => Ok(()),
+            found => Err(QueryError(format!(
+                "expected `{}`, found `{}`",
+                symbol, found
+            ))),
+        }
+    }
+
+    fn number(&mut self) -> Result<usize, QueryError> {
+        match self.next()? {
+            Token::Number(number) => Ok(number),
+            found => Err(QueryError(format!("expected a number, found `{}`", found))),
+        }
+    }
+
+    fn aggregate(&mut self) -> Result<Aggregate, QueryError> {
+        let name = match self.next()? {
+            Token::Word(word) => word,
+            found => {
+                return Err(QueryError(format!(
+                    "expected an aggregate, found `{}`",
+                    found
+                )))
+            }
+        };
+        if name == "count" {
+            return Ok(Aggregate::Count);
+        }
+        let wrap = match name.as_str() {
+            "sum" => Aggregate::Sum,
+            "min" => Aggregate::Min,
+            "max" => Aggregate::Max,
+            _ => return Err(QueryError(format!("unknown aggregate `{}`", name))),
+        };
+        self.expect_symbol("(")?;
+        let term = self.term()?;
+        self.expect_symbol(")")?;
+        Ok(wrap(term))
+    }
+
+    fn term(&mut self) -> Result<Term, QueryError> {
+        match self.next()? {
+            Token::Word(word) => match word.as_str() {
+                "id" => Ok(Term::Id),
+                "rounds" => Ok(Term::Rounds),
+                "power" => Ok(Term::Power),
+                "where" | "and" | "or" | "not" | "in" | "count" | "sum" | "min" | "max" => {
+                    Err(QueryError(format!("expected a term, found `{}`", word)))
+                }
+                color => {
+                    Ok(Term::Color(color.parse().map_err(|_| {
+                        QueryError(format!("invalid color `{}`", color))
+                    })?))
+                }
+            },
+            found => Err(QueryError(format!("expected a term, found `{}`", found))),
+        }
+    }
+
+    fn predicate(&mut self) -> Result<Predicate, QueryError> {
+        let mut left = self.conjunct()?;
+        while self.eat_word("or") {
+            let right = self.conjunct()?;
+            left = Predicate::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn conjunct(&mut self) -> Result<Predicate, QueryError> {
+        let mut left = self.negation()?;
+        while self.eat_word("and") {
+            let right = self.negation()?;
+            left = Predicate::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn negation(&mut self) -> Result<Predicate, QueryError> {
+        if self.eat_word("not") {
+            return Ok(Predicate::Not(Box::new(self.negation()?)));
+        }
+        if self.peek() == Some(&Token::Symbol("(")) {
+            self.position += 1;
+            let inner = self.predicate()?;
+            self.expect_symbol(")")?;
+            return Ok(inner);
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Predicate, QueryError> {
+        let term = self.term()?;
+        if self.eat_word("in") {
+            let low = self.number()?;
+            self.expect_symbol("..")?;
+            let high = self.number()?;
+            return Ok(Predicate::Between(term, low, high));
+        }
+        let op = match self.next()? {
+            Token::Symbol("<") => Op::Lt,
+            Token::Symbol("<=") => Op::Le,
+            Token::Symbol(">") => Op::Gt,
+            Token::Symbol(">=") => Op::Ge,
+            Token::Symbol("==") => Op::Eq,
+            Token::Symbol("!=") => Op::Ne,
+            found => {
+                return Err(QueryError(format!(
+                    "expected a comparison, found `{}`",
+                    found
+                )))
+            }
+        };
+        Ok(Predicate::Compare(term, op, self.number()?))
+    }
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            position: 0,
+        };
+        let aggregate = parser.aggregate()?;
+        let filter = if parser.eat_word("where") {
+            Some(parser.predicate()?)
+        } else {
+            None
+        };
+        if let Some(token) = parser.peek() {
+            return Err(QueryError(format!("unexpected `{}`", token)));
+        }
+        Ok(Query { aggregate, filter })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError(String);
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+impl From<QueryError> for Error {
+    fn from(err: QueryError) -> Self {
+        Error::new(ErrorKind::InvalidInput, err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::tests::EXAMPLE;
+    use crate::parse_games;
+
+    fn run(query: &str) -> usize {
+        let games = parse_games(EXAMPLE).unwrap();
+        let bag = Bag::default();
+        let query = query.parse::<Query>().unwrap();
+        query.check_colors(&bag).unwrap();
+        query.evaluate(&games, &bag)
+    }
+
+    #[test]
+    fn test_matches_parts() {
+        assert_eq!(
+            run("sum(id) where red <= 12 and green <= 13 and blue <= 14"),
+            8
+        );
+        assert_eq!(run("sum(power)"), 2286);
+    }
+
+    #[test]
+    fn test_filters() {
+        assert_eq!(run("count"), 5);
+        assert_eq!(run("count where blue > 10"), 1);
+        assert_eq!(run("sum(id) where rounds <= 2"), 5);
+        assert_eq!(run("sum(id) where id in 2..4"), 9);
+        assert_eq!(run("count where not (red > 12 or blue > 14)"), 3);
+        assert_eq!(run("count where red == 1 or red == 6 and green == 3"), 2);
+        assert_eq!(run("max(red) where id != 3"), 14);
+        assert_eq!(run("min(power)"), 12);
+        assert_eq!(run("max(rounds) where red > 100"), 0);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!("".parse::<Query>().is_err());
+        assert!("total".parse::<Query>().is_err());
+        assert!("sum(id".parse::<Query>().is_err());
+        assert!("count where".parse::<Query>().is_err());
+        assert!("count where blue".parse::<Query>().is_err());
+        assert!("count where blue > ten".parse::<Query>().is_err());
+        assert!("count where id in 1..".parse::<Query>().is_err());
+        assert!("count blue".parse::<Query>().is_err());
+        assert!("count where blue > 1 #".parse::<Query>().is_err());
+
+        let query = "count where yellow > 1".parse::<Query>().unwrap();
+        assert_eq!(
+            query.check_colors(&Bag::default()),
+            Err(QueryError("unknown color `yellow`".to_string()))
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::{Bag, Color, Draw, Game, Round};
+
+/// One color in one round that shows more cubes than the bag holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// 1-based, matching the order the rounds appear in the input.
+    pub round: usize,
+    pub color: Color,
+    pub observed: usize,
+    pub limit: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameReport {
+    pub id: usize,
+    pub violations: Vec<Violation>,
+    pub minimum_bag: Round,
+}
+
+impl GameReport {
+    pub fn new(bag: &Bag, game: &Game) -> Self {
+        let mut violations = Vec::new();
+        for (index, round) in game.rounds.iter().enumerate() {
+            for (color, observed) in round.iter() {
+                let limit = bag.get(color).unwrap_or(0);
+                if observed > limit {
+                    violations.push(Violation {
+                        round: index + 1,
+                        color: color.clone(),
+                        observed,
+                        limit,
+                    });
+                }
+            }
+        }
+
+        // Bag colors the game never shows stay in the minimum bag with a
+        // count of zero, so every report lists the same colors.
+        let colors: BTreeSet<&Color> = bag.colors().chain(game.colors()).collect();
+        let minimum_bag = colors
+            .into_iter()
+            .map(|color| Draw {
+                count: game.max_count(color),
+                color: color.clone(),
+            })
+            .collect();
+
+        GameReport {
+            id: game.id,
+            violations,
+            minimum_bag,
+        }
+    }
+
+    pub fn is_possible(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Feasibility of every game against a bag, printable as text via `Display`
+/// or as JSON via [`Report::to_json`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub bag: Bag,
+    pub games: Vec<GameReport>,
+}
+
+impl Report {
+    pub fn new(bag: &Bag, games: &[Game]) -> Self {
+        Report {
+            bag: bag.clone(),
+            games: games
+                .iter()
+                .map(|game| GameReport::new(bag, game))
+                .collect(),
+        }
+    }
+
+    pub fn impossible(&self) -> impl Iterator<Item = &GameReport> + '_ {
+        self.games.iter().filter(|game| !game.is_possible())
+    }
+
+    pub fn to_json(&self) -> String {
+        let bag = round_json(self.bag.iter());
+        let games: Vec<String> = self
+            .games
+            .iter()
+            .map(|game| {
+                let violations: Vec<String> = game
+                    .violations
+                    .iter()
+                    .map(|v| {
+                        format!(
+                            r#"{{"round":{},"color":"{}","observed":{},"limit":{}}}"#,
+                            v.round, v.color, v.observed, v.limit
+                        )
+                    })
+                    .collect();
+                format!(
+                    r#"{{"id":{},"possible":{},"violations":[{}],"minimum_bag":{}}}"#,
+                    game.id,
+                    game.is_possible(),
+                    violations.join(","),
+                    round_json(game.minimum_bag.iter())
+                )
+            })
+            .collect();
+        format!(r#"{{"bag":{},"games":[{}]}}"#, bag, games.join(","))
+    }
+}
+
+/// Colors are restricted to lowercase ASCII words, so they never need
+/// escaping inside a JSON string.
+fn round_json<'a>(counts: impl Iterator<Item = (&'a Color, usize)>) -> String {
+    let entries: Vec<String> = counts
+        .map(|(color, count)| format!(r#""{}":{}"#, color, count))
+        .collect();
+    format!("{{{}}}", entries.join(","))
+}
+
+fn round_text<'a>(counts: impl Iterator<Item = (&'a Color, usize)>) -> String {
+    let entries: Vec<String> = counts
+        .map(|(color, count)| format!("{} {}", count, color))
+        .collect();
+    entries.join(", ")
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "bag: {}", round_text(self.bag.iter()))?;
+        for game in &self.games {
+            let status = if game.is_possible() {
+                "possible"
+            } else {
+                "impossible"
+            };
+            writeln!(f, "Game {}: {}", game.id, status)?;
+            for v in &game.violations {
+                writeln!(
+                    f,
+                    "  round {}: {} {} drawn, bag holds {}",
+                    v.round, v.observed, v.color, v.limit
+                )?;
+            }
+            writeln!(f, "  minimum bag: {}", round_text(game.minimum_bag.iter()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::tests::EXAMPLE;
+    use crate::parse_games;
+
+    #[test]
+    fn test_violations() {
+        let games = parse_games(EXAMPLE).unwrap();
+        let report = Report::new(&Bag::default(), &games);
+
+        let impossible: Vec<usize> = report.impossible().map(|game| game.id).collect();
+        assert_eq!(impossible, vec![3, 4]);
+
+        assert_eq!(
+            report.games[2].violations,
+            vec![Violation {
+                round: 1,
+                color: "red".parse().unwrap(),
+                observed: 20,
+                limit: 12,
+            }]
+        );
+        assert_eq!(
+            report.games[3].violations,
+            vec![
+                Violation {
+                    round: 3,
+                    color: "blue".parse().unwrap(),
+                    observed: 15,
+                    limit: 14,
+                },
+                Violation {
+                    round: 3,
+                    color: "red".parse().unwrap(),
+                    observed: 14,
+                    limit: 12,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_minimum_bag_lists_bag_colors() {
+        let games = parse_games("Game 7: 2 red; 4 red, 1 green").unwrap();
+        let report = Report::new(&Bag::default(), &games);
+
+        let minimum: Vec<(String, usize)> = report.games[0]
+            .minimum_bag
+            .iter()
+            .map(|(color, count)| (color.to_string(), count))
+            .collect();
+        assert_eq!(
+            minimum,
+            vec![
+                ("blue".to_string(), 0),
+                ("green".to_string(), 1),
+                ("red".to_string(), 4)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_text_and_json() {
+        let games = parse_games("Game 1: 13 red; 1 blue\nGame 2: 1 green").unwrap();
+        let report = Report::new(&"red=12,green=1,blue=1".parse().unwrap(), &games);
+
+        assert_eq!(
+            report.to_string(),
+            "bag: 1 blue, 1 green, 12 red
+Game 1: impossible
+  round 1: 13 red drawn, bag holds 12
+  minimum bag: 1 blue, 0 green, 13 red
+Game 2: possible
+  minimum bag: 0 blue, 1 green, 0 red
+"
+        );
+        assert_eq!(
+            report.to_json(),
+            concat!(
+                r#"{"bag":{"blue":1,"green":1,"red":12},"games":["#,
+                r#"{"id":1,"possible":false,"violations":[{"round":1,"color":"red","observed":13,"limit":12}],"minimum_bag":{"blue":1,"green":0,"red":13}},"#,
+                r#"{"id":2,"possible":true,"violations":[],"minimum_bag":{"blue":0,"green":1,"red":0}}]}"#
+            )
+        );
+    }
+}
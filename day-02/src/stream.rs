@@ -0,0 +1,238 @@
+use std::io::prelude::*;
+use std::io::{Error, ErrorKind};
+use std::thread;
+use std::time::Duration;
+
+use crate::{Bag, BagError, Game};
+
+/// Running part 1 and part 2 answers over every game seen so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Totals {
+    pub games: usize,
+    /// Sum of the ids of the games the bag allows (part 1).
+    pub id_sum: usize,
+    /// Sum of the powers of every game (part 2).
+    pub power_sum: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Tally {
+    bag: Bag,
+    totals: Totals,
+}
+
+impl Tally {
+    pub fn new(bag: Bag) -> Self {
+        Tally {
+            bag,
+            totals: Totals::default(),
+        }
+    }
+
+    pub fn totals(&self) -> Totals {
+        self.totals
+    }
+
+    /// Folds one more game into the totals. A game showing a color the bag
+    /// does not know is an error and leaves the totals as they were, so the
+    /// caller may skip it and go on adding.
+    pub fn add(&mut self, game: &Game) -> Result<Totals, BagError> {
+        self.bag.check_colors(std::slice::from_ref(game))?;
+
+        self.totals.games += 1;
+        if self.bag.allows(game) {
+            self.totals.id_sum += game.id;
+        }
+        self.totals.power_sum += self.bag.power(game);
+        Ok(self.totals)
+    }
+}
+
+/// Calls `on_line` for every complete line read from `reader`, without the
+/// line ending.
+///
+/// With `poll` set, reaching the end of the input waits that long and tries
+/// again instead of returning, like `tail -f`. A line is only handed over once
+/// its newline has arrived, so a writer caught halfway through a line is
+/// never seen. Without `poll`, a final line lacking a newline is still passed
+/// on.
+pub fn tail_lines<R, F>(mut reader: R, poll: Option<Duration>, mut on_line: F) -> Result<(), Error>
+where
+    R: BufRead,
+    F: FnMut(&str) -> Result<(), Error>,
+{
+    let mut line = String::new();
+    loop {
+        let read = reader.read_line(&mut line)?;
+        if line.ends_with('\n') {
+            on_line(line.trim_end_matches(['\n', '\r']))?;
+            line.clear();
+        } else if read == 0 {
+            match poll {
+                Some(interval) => thread::sleep(interval),
+                None => {
+                    if !line.is_empty() {
+                        on_line(&line)?;
+                    }
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+/// Parses each non-blank line of `reader` as a game and calls `on_update`
+/// with the game and the totals after it.
+///
+/// Stops at the first line that is not a game or shows a color the bag does
+/// not know, returning its error; the games before it have already been
+/// handed to `on_update`.
+pub fn stream_games<R, F>(
+    reader: R,
+    bag: Bag,
+    poll: Option<Duration>,
+    mut on_update: F,
+) -> Result<Totals, Error>
+where
+    R: BufRead,
+    F: FnMut(&Game, Totals) -> Result<(), Error>,
+{
+    let mut tally = Tally::new(bag);
+    let mut row = 0;
+    tail_lines(reader, poll, |line| {
+        row += 1;
+        if line.trim().is_empty() {
+            return Ok(());
+        }
+        let game = line
+            .parse::<Game>()
+            .map_err(|err| Error::new(ErrorKind::InvalidData, format!("line {}: {}", row, err)))?;
+        let totals = tally.add(&game)?;
+        on_update(&game, totals)
+    })?;
+    Ok(tally.totals())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::tests::EXAMPLE;
+    use crate::parse_games;
+    use std::collections::VecDeque;
+    use std::io::{BufReader, Read};
+
+    /// Hands out one chunk per read, with an end-of-file between chunks, and
+    /// fails once every chunk is used so a polling reader stops.
+    struct Chunks(VecDeque<Option<&'static str>>);
+
+    impl Read for Chunks {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+            match self.0.pop_front() {
+                Some(Some(chunk)) => {
+                    buf[..chunk.len()].copy_from_slice(chunk.as_bytes());
+                    Ok(chunk.len())
+                }
+                Some(None) => Ok(0),
+                None => Err(Error::new(ErrorKind::UnexpectedEof, "done")),
+            }
+        }
+    }
+
+    #[test]
+    fn test_stream_example() {
+        let mut updates = Vec::new();
+        let totals = stream_games(EXAMPLE.as_bytes(), Bag::default(), None, |game, totals| {
+            updates.push((game.id, totals.id_sum, totals.power_sum));
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(
+            updates,
+            vec![
+                (1, 1, 48),
+                (2, 3, 60),
+                (3, 3, 1620),
+                (4, 3, 2250),
+                (5, 8, 2286)
+            ]
+        );
+        assert_eq!(
+            totals,
+            Totals {
+                games: 5,
+                id_sum: 8,
+                power_sum: 2286
+            }
+        );
+    }
+
+    #[test]
+    fn test_tail_waits_for_newline() {
+        let reader = Chunks(VecDeque::from([
+            Some("Game 1: 1 r"),
+            None,
+            Some("ed\nGame 2"),
+            None,
+            None,
+            Some(": 2 blue\n\nGame 3: 1 green"),
+            None,
+        ]));
+
+        let mut lines = Vec::new();
+        let result = tail_lines(
+            BufReader::new(reader),
+            Some(Duration::from_millis(1)),
+            |line| {
+                lines.push(line.to_string());
+                Ok(())
+            },
+        );
+
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(lines, vec!["Game 1: 1 red", "Game 2: 2 blue", ""]);
+    }
+
+    #[test]
+    fn test_tail_without_poll_keeps_last_line() {
+        let mut lines = Vec::new();
+        tail_lines("a\r\nb".as_bytes(), None, |line| {
+            lines.push(line.to_string());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(lines, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_stream_reports_bad_line() {
+        let input = "Game 1: 1 red\n\nGame 2: 1 yellow\n";
+        let err = stream_games(input.as_bytes(), Bag::default(), None, |_, _| Ok(())).unwrap_err();
+        assert_eq!(err.to_string(), "game 2 shows unknown color `yellow`");
+
+        let input = "Game 1: 1 red\n\nGame x: 1 red\n";
+        let err = stream_games(input.as_bytes(), Bag::default(), None, |_, _| Ok(())).unwrap_err();
+        assert_eq!(err.to_string(), "line 3: invalid game id `x`");
+    }
+
+    #[test]
+    fn test_tally_skips_unknown_color() {
+        let mut tally = Tally::new(Bag::default());
+        let games = parse_games(
+            "Game 1: 1 red, 1 green, 1 blue\nGame 2: 1 yellow\nGame 3: 2 red, 1 green, 3 blue",
+        )
+        .unwrap();
+        assert!(tally.add(&games[0]).is_ok());
+        let before = tally.totals();
+        assert!(tally.add(&games[1]).is_err());
+        assert_eq!(tally.totals(), before);
+        assert_eq!(
+            tally.add(&games[2]).unwrap(),
+            Totals {
+                games: 2,
+                id_sum: 4,
+                power_sum: 7
+            }
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use std::env;
+use std::io::{Error, ErrorKind};
+
+use day_03::{ParseOptions, Renderer, Schematic, Style, Window};
+
+/// Usage: `render [--markup] [--window <row>,<col>,<radius>] [--minus symbol|sign] [input]`
+///
+/// Prints the schematic with part numbers, other numbers, gears and symbols
+/// colored apart. `--markup` (or a set `NO_COLOR`) swaps the colors for
+/// tags; `--window` only prints the cells around one coordinate.
+fn main() -> Result<(), Error> {
+    let mut style = if env::var_os("NO_COLOR").is_some() {
+        Style::Markup
+    } else {
+        Style::Color
+    };
+    let mut window = None;
+    let mut options = ParseOptions::default();
+
+    let positional = aoc_cli::parse(env::args().skip(1), |values| {
+        match values.flag() {
+            "--markup" => style = Style::Markup,
+            "--window" => {
+                let value = values.value()?;
+                window = Some(parse_window(&value).ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "expected `--window <row>,<col>,<radius>`, found `{}`",
+                            value
+                        ),
+                    )
+                })?);
+            }
+            "--minus" => options.minus = values.parse()?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    let path = aoc_cli::input_path(positional, "src/day-03.txt")?;
+
+    let input = aoc_cli::read_input(&path)?;
+    let schematic = Schematic::parse_with(&input, &options)?;
+    print!("{}", Renderer::new(&schematic).render(style, window));
+    Ok(())
+}
+
+fn parse_window(value: &str) -> Option<Window> {
+    let parts: Vec<usize> = value
+        .split(',')
+        .map(|part| part.trim().parse().ok())
+        .collect::<Option<Vec<usize>>>()?;
+    match parts[..] {
+        [row, col, radius] => Some(Window { row, col, radius }),
+        _ => None,
+    }
+}
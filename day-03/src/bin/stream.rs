@@ -0,0 +1,34 @@
+use std::env;
+use std::io::Error;
+
+use day_03::{stream_schematic, Event, ParseOptions};
+
+/// Usage: `stream [--minus symbol|sign] [input]`
+///
+/// Reads the schematic from `input` (stdin when missing or `-`) three rows at
+/// a time and prints every part number and gear as soon as its row is done,
+/// then both answers.
+fn main() -> Result<(), Error> {
+    let mut options = ParseOptions::default();
+    let positional = aoc_cli::parse(env::args().skip(1), |values| {
+        match values.flag() {
+            "--minus" => options.minus = values.parse()?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    let path = aoc_cli::input_path(positional, "-")?;
+
+    let print = |event: Event| {
+        match event {
+            Event::Part(number) => {
+                println!("part {} at {},{}", number.value, number.row, number.start)
+            }
+            Event::Gear { row, col, ratio } => println!("gear {} at {},{}", ratio, row, col),
+        }
+        Ok(())
+    };
+    let totals = stream_schematic(aoc_cli::open_reader(&path)?, &options, print)?;
+    println!("part1 {} part2 {}", totals.part_sum, totals.gear_sum);
+    Ok(())
+}
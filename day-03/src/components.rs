@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+
+use crate::{Number, Schematic};
+
+/// The smallest rectangle holding every cell of a component, inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub top: usize,
+    pub left: usize,
+    pub bottom: usize,
+    pub right: usize,
+}
+
+impl Bounds {
+    fn cell(row: usize, col: usize) -> Self {
+        Bounds {
+            top: row,
+            left: col,
+            bottom: row,
+            right: col,
+        }
+    }
+
+    fn extend(&mut self, other: Bounds) {
+        self.top = self.top.min(other.top);
+        self.left = self.left.min(other.left);
+        self.bottom = self.bottom.max(other.bottom);
+        self.right = self.right.max(other.right);
+    }
+}
+
+/// A machine: numbers and symbols joined by touching each other, directly
+/// or through other members.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    pub numbers: Vec<Number>,
+    /// `(row, col, symbol)` of every member symbol.
+    pub symbols: Vec<(usize, usize, String)>,
+    pub bounds: Bounds,
+}
+
+impl Component {
+    pub fn sum(&self) -> i128 {
+        self.numbers.iter().map(|number| number.value as i128).sum()
+    }
+
+    /// How many times each symbol appears in the component.
+    pub fn symbol_counts(&self) -> BTreeMap<&str, usize> {
+        let mut counts = BTreeMap::new();
+        for (_, _, symbol) in &self.symbols {
+            *counts.entry(symbol.as_str()).or_default() += 1;
+        }
+        counts
+    }
+}
+
+struct DisjointSet {
+    parent: Vec<usize>,
+}
+
+impl DisjointSet {
+    fn new(size: usize) -> Self {
+        DisjointSet {
+            parent: (0..size).collect(),
+        }
+    }
+
+    fn find(&mut self, mut node: usize) -> usize {
+        while self.parent[node] != node {
+            self.parent[node] = self.parent[self.parent[node]];
+            node = self.parent[node];
+        }
+        node
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a != b {
+            self.parent[a.max(b)] = a.min(b);
+        }
+    }
+}
+
+impl Schematic {
+    /// Groups every number and symbol into connected components.
+    ///
+    /// Edges are the same number-to-symbol contacts [`Schematic::hubs`] and
+    /// the gear ratio sum use: two numbers side by side, or two symbols side
+    /// by side, are not joined unless a symbol links them. A number touching
+    /// no symbol is a component on its own.
+    ///
+    /// Components are ordered by their first member in reading order.
+    pub fn components(&self) -> Vec<Component> {
+        let numbers = self.numbers();
+        let symbols: Vec<(usize, usize, &str)> = self.symbols().collect();
+
+        // Nodes `0..numbers.len()` are numbers, the rest are symbols.
+        let mut set = DisjointSet::new(numbers.len() + symbols.len());
+        for (s, &(row, col, _)) in symbols.iter().enumerate() {
+            for n in self.number_indices_around(row, col) {
+                set.union(numbers.len() + s, n);
+            }
+        }
+
+        let mut components: BTreeMap<usize, Component> = BTreeMap::new();
+        for (n, number) in numbers.iter().enumerate() {
+            let bounds = Bounds {
+                top: number.row,
+                left: number.start,
+                bottom: number.row,
+                right: number.end - 1,
+            };
+            let component = components.entry(set.find(n)).or_insert_with(|| Component {
+                numbers: Vec::new(),
+                symbols: Vec::new(),
+                bounds,
+            });
+            component.numbers.push(*number);
+            component.bounds.extend(bounds);
+        }
+        for (s, &(row, col, symbol)) in symbols.iter().enumerate() {
+            let bounds = Bounds::cell(row, col);
+            let component = components
+                .entry(set.find(numbers.len() + s))
+                .or_insert_with(|| Component {
+                    numbers: Vec::new(),
+                    symbols: Vec::new(),
+                    bounds,
+                });
+            component.symbols.push((row, col, symbol.to_string()));
+            component.bounds.extend(bounds);
+        }
+
+        let mut components: Vec<Component> = components.into_values().collect();
+        components.sort_by_key(|component| {
+            let first_number = component.numbers.first().map(|n| (n.row, n.start));
+            let first_symbol = component.symbols.first().map(|&(row, col, _)| (row, col));
+            first_number.into_iter().chain(first_symbol).min()
+        });
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::tests::EXAMPLE;
+
+    #[test]
+    fn test_example_components() {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        let components = schematic.components();
+
+        let members: Vec<(Vec<i64>, String)> = components
+            .iter()
+            .map(|component| {
+                (
+                    component.numbers.iter().map(|n| n.value).collect(),
+                    component
+                        .symbols
+                        .iter()
+                        .map(|(_, _, s)| s.as_str())
+                        .collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            members,
+            vec![
+                (vec![467, 35], "*".to_string()),
+                (vec![114], "".to_string()),
+                (vec![633], "#".to_string()),
+                (vec![617], "*".to_string()),
+                (vec![592], "+".to_string()),
+                (vec![58], "".to_string()),
+                (vec![755, 598], "*".to_string()),
+                (vec![664], "$".to_string()),
+            ]
+        );
+
+        assert_eq!(components[0].sum(), 502);
+        assert_eq!(
+            components[0].bounds,
+            Bounds {
+                top: 0,
+                left: 0,
+                bottom: 2,
+                right: 3
+            }
+        );
+        let part_sum: i128 = components
+            .iter()
+            .filter(|component| !component.symbols.is_empty())
+            .map(Component::sum)
+            .sum();
+        assert_eq!(part_sum, 4361);
+    }
+
+    #[test]
+    fn test_transitive_machine() {
+        let schematic = Schematic::parse(
+            "11.22..
+..#..*.
+.3.....
+.......
++......",
+        )
+        .unwrap();
+        let components = schematic.components();
+        assert_eq!(components.len(), 2);
+
+        let machine = &components[0];
+        let values: Vec<i64> = machine.numbers.iter().map(|n| n.value).collect();
+        assert_eq!(values, vec![11, 22, 3]);
+        assert_eq!(
+            machine.symbol_counts(),
+            BTreeMap::from([("#", 1), ("*", 1)])
+        );
+        assert_eq!(
+            machine.bounds,
+            Bounds {
+                top: 0,
+                left: 0,
+                bottom: 2,
+                right: 5
+            }
+        );
+        assert_eq!(machine.sum(), 36);
+
+        assert!(components[1].numbers.is_empty());
+        assert_eq!(components[1].symbols, vec![(4, 0, "+".to_string())]);
+        assert_eq!(components.last().unwrap().bounds, Bounds::cell(4, 0));
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{ParseOptions, ParseSchematicError, Schematic};
+
+/// A schematic that keeps the part 1 and part 2 answers up to date while its
+/// cells are edited.
+///
+/// An edit can only change the numbers on its own row near the edited cell,
+/// the symbols among those cells (a `-` read as a sign becomes a symbol once
+/// its digits are gone), and so only numbers and gears within one cell of
+/// that stretch. [`Editor::set`] takes their share out of the totals, applies
+/// the edit and adds their new share back, without looking at the rest of
+/// the board.
+#[derive(Debug, Clone)]
+pub struct Editor {
+    schematic: Schematic,
+    part_sum: i128,
+    gear_sum: i128,
+}
+
+/// A rectangle of cells, `rows` and `cols` both half-open.
+#[derive(Debug, Clone, Copy)]
+struct Region {
+    rows: (usize, usize),
+    cols: (usize, usize),
+}
+
+impl Editor {
+    pub fn new(schematic: Schematic) -> Self {
+        let whole = Region {
+            rows: (0, schematic.height()),
+            cols: (0, schematic.width()),
+        };
+        let mut editor = Editor {
+            schematic,
+            part_sum: 0,
+            gear_sum: 0,
+        };
+        (editor.part_sum, editor.gear_sum) = editor.totals_within(whole);
+        editor
+    }
+
+    pub fn parse_with(input: &str, options: &ParseOptions) -> Result<Self, ParseSchematicError> {
+        Ok(Self::new(Schematic::parse_with(input, options)?))
+    }
+
+    pub fn schematic(&self) -> &Schematic {
+        &self.schematic
+    }
+
+    /// Sum of every part number (part 1).
+    pub fn part_sum(&self) -> i128 {
+        self.part_sum
+    }
+
+    /// Sum of the gear ratios (part 2).
+    pub fn gear_sum(&self) -> i128 {
+        self.gear_sum
+    }
+
+    /// Overwrites the cell at `(row, col)` with the single grapheme `cell`
+    /// and updates both totals. An edit that would make a number too long
+    /// for an `i64` is an error and changes nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(row, col)` is outside the grid or `cell` is not exactly
+    /// one grapheme.
+    pub fn set(&mut self, row: usize, col: usize, cell: &str) -> Result<(), ParseSchematicError> {
+        let (lo, hi) = self.schematic.edit_span(row, col);
+        let region = Region {
+            rows: (
+                row.saturating_sub(1),
+                (row + 2).min(self.schematic.height()),
+            ),
+            cols: (lo.saturating_sub(1), (hi + 1).min(self.schematic.width())),
+        };
+
+        let (parts, gears) = self.totals_within(region);
+        self.schematic.set(row, col, cell)?;
+        let (new_parts, new_gears) = self.totals_within(region);
+
+        self.part_sum += new_parts - parts;
+        self.gear_sum += new_gears - gears;
+        Ok(())
+    }
+
+    /// What the part numbers with a cell in `region` and the gears inside
+    /// it contribute to the two totals.
+    fn totals_within(&self, region: Region) -> (i128, i128) {
+        let mut part_sum = 0;
+        let mut gear_sum = 0;
+        for row in region.rows.0..region.rows.1 {
+            for col in region.cols.0..region.cols.1 {
+                if let Some(number) = self.schematic.number_at(row, col) {
+                    // Count each number at its first cell in the region only.
+                    let first = col == region.cols.0.max(number.start);
+                    if first && self.schematic.is_part(number) {
+                        part_sum += number.value as i128;
+                    }
+                } else if self.schematic.get(row, col) == Some("*")
+                    && self.schematic.is_symbol_at(row, col)
+                {
+                    let numbers = self.schematic.numbers_around(row, col);
+                    if numbers.len() == 2 {
+                        gear_sum += numbers[0].value as i128 * numbers[1].value as i128;
+                    }
+                }
+            }
+        }
+        (part_sum, gear_sum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::tests::EXAMPLE;
+    use crate::{calculate_part_numbers, sum_gear_ratios, Minus};
+    use aoc_testing::Lcg;
+
+    fn text(schematic: &Schematic) -> String {
+        (0..schematic.height())
+            .map(|row| {
+                (0..schematic.width())
+                    .map(|col| schematic.get(row, col).unwrap())
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_single_edits() {
+        let mut editor = Editor::parse_with(EXAMPLE, &ParseOptions::default()).unwrap();
+        assert_eq!((editor.part_sum(), editor.gear_sum()), (4361, 467835));
+
+        // A `*` under 114 makes it a part and pairs it with 633.
+        editor.set(1, 6, "*").unwrap();
+        assert_eq!(
+            (editor.part_sum(), editor.gear_sum()),
+            (4361 + 114, 467835 + 114 * 633)
+        );
+
+        // Filling the gap joins 467 and 114 into one number on both gears.
+        editor.set(0, 3, "0").unwrap();
+        editor.set(0, 4, "0").unwrap();
+        assert_eq!(editor.schematic().number_at(0, 5).unwrap().value, 46700114);
+        assert_eq!(
+            (editor.part_sum(), editor.gear_sum()),
+            (
+                4361 - 467 + 46700114,
+                46700114 * 35 + 46700114 * 633 + 755 * 598
+            )
+        );
+
+        // Splitting it again restores the original board.
+        editor.set(0, 3, ".").unwrap();
+        editor.set(0, 4, ".").unwrap();
+        editor.set(1, 6, ".").unwrap();
+        assert_eq!((editor.part_sum(), editor.gear_sum()), (4361, 467835));
+    }
+
+    #[test]
+    fn test_sign_released_by_edit() {
+        let options = ParseOptions { minus: Minus::Sign };
+        let mut editor = Editor::parse_with("5-3\n...", &options).unwrap();
+        assert_eq!(editor.part_sum(), 0);
+
+        // Without its digit the `-` turns into a symbol next to 5.
+        editor.set(0, 2, ".").unwrap();
+        assert_eq!(editor.part_sum(), 5);
+        editor.set(0, 2, "7").unwrap();
+        assert_eq!(editor.part_sum(), 0);
+    }
+
+    #[test]
+    fn test_random_edits_match_full_recompute() {
+        let palette = [
+            ".", ".", ".", ".", ".", ".", ".", ".", ".", ".", "0", "1", "2", "3", "4", "5", "6",
+            "7", "8", "9", "*", "-", "#", "§", "e\u{301}",
+        ];
+        for minus in [Minus::Symbol, Minus::Sign] {
+            let options = ParseOptions { minus };
+            let mut editor = Editor::parse_with(EXAMPLE, &options).unwrap();
+            let mut rng = Lcg::new(39);
+            for _ in 0..2000 {
+                let (row, col) = (rng.below(10), rng.below(10));
+                let cell = palette[rng.below(palette.len())];
+                editor.set(row, col, cell).unwrap();
+
+                let board = text(editor.schematic());
+                assert_eq!(
+                    editor.part_sum(),
+                    calculate_part_numbers(&board, &options).unwrap(),
+                    "{}",
+                    board
+                );
+                assert_eq!(
+                    editor.gear_sum(),
+                    sum_gear_ratios(&board, &options).unwrap(),
+                    "{}",
+                    board
+                );
+                assert_eq!(
+                    editor.schematic().numbers().len(),
+                    Schematic::parse_with(&board, &options)
+                        .unwrap()
+                        .numbers()
+                        .len()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{Number, Schematic};
+
+/// Which symbols can act as a hub.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SymbolClass {
+    /// Any symbol at all.
+    Any,
+    /// Only cells that are exactly this `char`, e.g. `*` for gears.
+    Char(char),
+    /// Cells that are exactly one of these `char`s.
+    OneOf(Vec<char>),
+}
+
+impl SymbolClass {
+    /// Whether `class` covers `cell`, which must already be known to be a
+    /// symbol.
+    pub fn contains(&self, cell: &str) -> bool {
+        let mut chars = cell.chars();
+        let single = match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(c),
+            _ => None,
+        };
+        match self {
+            SymbolClass::Any => true,
+            SymbolClass::Char(symbol) => single == Some(*symbol),
+            SymbolClass::OneOf(symbols) => single.is_some_and(|c| symbols.contains(&c)),
+        }
+    }
+}
+
+/// How many distinct numbers a symbol must touch to count as a hub.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl Arity {
+    pub fn accepts(self, count: usize) -> bool {
+        match self {
+            Arity::Exactly(k) => count == k,
+            Arity::AtLeast(k) => count >= k,
+        }
+    }
+}
+
+/// Folds the numbers around a hub into one value, in an `i128` so that the
+/// product of two `i64`s always fits.
+pub trait Combine {
+    fn combine(&self, values: &[i64]) -> i128;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Combiner {
+    Product,
+    Sum,
+    Max,
+}
+
+impl Combine for Combiner {
+    /// # Panics
+    ///
+    /// `Product` panics if the product of more than two numbers overflows an
+    /// `i128`.
+    fn combine(&self, values: &[i64]) -> i128 {
+        let mut values = values.iter().map(|&value| value as i128);
+        match self {
+            Combiner::Product => values
+                .try_fold(1i128, i128::checked_mul)
+                .unwrap_or_else(|| {
+                    panic!("the product of the numbers around a hub overflows an i128")
+                }),
+            Combiner::Sum => values.sum(),
+            Combiner::Max => values.max().unwrap_or(0),
+        }
+    }
+}
+
+impl<F: Fn(&[i64]) -> i128> Combine for F {
+    fn combine(&self, values: &[i64]) -> i128 {
+        self(values)
+    }
+}
+
+/// A symbol together with the numbers touching it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hub<'a> {
+    pub row: usize,
+    pub col: usize,
+    pub symbol: &'a str,
+    pub numbers: Vec<&'a Number>,
+}
+
+impl Hub<'_> {
+    pub fn values(&self) -> Vec<i64> {
+        self.numbers.iter().map(|number| number.value).collect()
+    }
+}
+
+impl Schematic {
+    /// Every symbol in `class` touching a number count accepted by `arity`,
+    /// in reading order.
+    pub fn hubs(&self, class: &SymbolClass, arity: Arity) -> Vec<Hub<'_>> {
+        self.symbols()
+            .filter(|&(_, _, symbol)| class.contains(symbol))
+            .map(|(row, col, symbol)| Hub {
+                row,
+                col,
+                symbol,
+                numbers: self.numbers_around(row, col),
+            })
+            .filter(|hub| arity.accepts(hub.numbers.len()))
+            .collect()
+    }
+
+    /// Sum over the hubs of `combiner` applied to their numbers.
+    pub fn sum_hubs<C: Combine>(&self, class: &SymbolClass, arity: Arity, combiner: &C) -> i128 {
+        self.hubs(class, arity)
+            .iter()
+            .map(|hub| combiner.combine(&hub.values()))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::tests::EXAMPLE;
+
+    #[test]
+    fn test_gears() {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        let gears = schematic.hubs(&SymbolClass::Char('*'), Arity::Exactly(2));
+        let positions: Vec<(usize, usize)> = gears.iter().map(|hub| (hub.row, hub.col)).collect();
+        assert_eq!(positions, vec![(1, 3), (8, 5)]);
+        assert_eq!(gears[0].values(), vec![467, 35]);
+        assert_eq!(
+            schematic.sum_hubs(
+                &SymbolClass::Char('*'),
+                Arity::Exactly(2),
+                &Combiner::Product
+            ),
+            467835
+        );
+    }
+
+    #[test]
+    fn test_classes_and_arity() {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            schematic.hubs(&SymbolClass::Any, Arity::AtLeast(1)).len(),
+            6
+        );
+        assert_eq!(
+            schematic.hubs(&SymbolClass::Any, Arity::AtLeast(2)).len(),
+            2
+        );
+        assert_eq!(
+            schematic.sum_hubs(
+                &SymbolClass::OneOf(vec!['#', '$']),
+                Arity::Exactly(1),
+                &Combiner::Sum
+            ),
+            633 + 664
+        );
+        assert_eq!(
+            schematic.sum_hubs(&SymbolClass::Any, Arity::AtLeast(1), &Combiner::Max),
+            467 + 617 + 633 + 592 + 664 + 755
+        );
+    }
+
+    #[test]
+    fn test_products_are_widened() {
+        let schematic = Schematic::parse("3\n*4611686018427387904\n4").unwrap();
+        assert_eq!(
+            schematic.sum_hubs(&SymbolClass::Any, Arity::AtLeast(2), &Combiner::Product),
+            (1i128 << 62) * 4 * 3
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows an i128")]
+    fn test_product_overflow_panics() {
+        let schematic =
+            Schematic::parse("9223372036854775807*9223372036854775807\n9223372036854775807")
+                .unwrap();
+        schematic.sum_hubs(&SymbolClass::Any, Arity::AtLeast(3), &Combiner::Product);
+    }
+
+    #[test]
+    fn test_three_way_hubs_with_custom_combiner() {
+        let schematic = Schematic::parse(
+            "1.2.3
+.#.#.
+4..5.",
+        )
+        .unwrap();
+        let hubs = schematic.hubs(&SymbolClass::Char('#'), Arity::Exactly(3));
+        assert_eq!(hubs.len(), 2);
+        assert_eq!(hubs[0].values(), vec![1, 2, 4]);
+        assert_eq!(hubs[1].values(), vec![2, 3, 5]);
+        assert_eq!(
+            schematic.sum_hubs(&SymbolClass::Char('#'), Arity::Exactly(3), &Combiner::Sum),
+            17
+        );
+
+        let sum_of_squares = |values: &[i64]| values.iter().map(|&v| v as i128 * v as i128).sum();
+        assert_eq!(
+            schematic.sum_hubs(&SymbolClass::Char('#'), Arity::AtLeast(3), &sum_of_squares),
+            1 + 4 + 16 + 4 + 9 + 25
+        );
+    }
+}
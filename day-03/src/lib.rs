@@ -0,0 +1,110 @@
+mod components;
+mod editor;
+mod hubs;
+mod render;
+mod schematic;
+mod stream;
+
+pub use components::{Bounds, Component};
+pub use editor::Editor;
+pub use hubs::{Arity, Combine, Combiner, Hub, SymbolClass};
+pub use render::{Category, Renderer, Style, Window};
+pub use schematic::{
+    is_symbol, Minus, Number, ParseMinusError, ParseOptions, ParseSchematicError, Schematic,
+};
+pub use stream::{stream_schematic, Event, Totals};
+
+/// Sum of every number touching a symbol (part 1), wide enough that no
+/// count of `i64` part numbers can overflow it.
+pub fn calculate_part_numbers(
+    input: &str,
+    options: &ParseOptions,
+) -> Result<i128, ParseSchematicError> {
+    let schematic = Schematic::parse_with(input, options)?;
+    Ok(schematic
+        .part_numbers()
+        .map(|number| number.value as i128)
+        .sum())
+}
+
+/// Sum of `a * b` over every `*` touching exactly two numbers `a` and `b`
+/// (part 2).
+pub fn sum_gear_ratios(input: &str, options: &ParseOptions) -> Result<i128, ParseSchematicError> {
+    let schematic = Schematic::parse_with(input, options)?;
+    Ok(schematic.sum_hubs(
+        &SymbolClass::Char('*'),
+        Arity::Exactly(2),
+        &Combiner::Product,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::tests::EXAMPLE;
+
+    const SIGNED: &str = "467..114..
+...*......
+..35..633.
+......#...
+-617*.....
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+..........
+10*-2.-3..";
+
+    #[test]
+    fn test_calculate_part_numbers() {
+        assert_eq!(
+            calculate_part_numbers(EXAMPLE, &ParseOptions::default()).unwrap(),
+            4361
+        );
+    }
+
+    #[test]
+    fn test_sum_gear_ratios() {
+        assert_eq!(
+            sum_gear_ratios(EXAMPLE, &ParseOptions::default()).unwrap(),
+            467835
+        );
+    }
+
+    #[test]
+    fn test_large_gear_ratios() {
+        let big = i64::MAX as i128;
+        let input = "9223372036854775807*9223372036854775807";
+        let options = ParseOptions::default();
+        assert_eq!(sum_gear_ratios(input, &options).unwrap(), big * big);
+        assert_eq!(calculate_part_numbers(input, &options).unwrap(), 2 * big);
+
+        let sign = ParseOptions { minus: Minus::Sign };
+        let input = "-9223372036854775808*-9223372036854775808";
+        assert_eq!(sum_gear_ratios(input, &sign).unwrap(), 1 << 126);
+    }
+
+    #[test]
+    fn test_minus_semantics_agree_across_parts() {
+        let symbol = ParseOptions {
+            minus: Minus::Symbol,
+        };
+        // `-` is a symbol: 617 still touches `*`, and 10, 2 and 3 all touch a
+        // `*` or `-`. The bottom `*` only touches 10, so it is no gear.
+        assert_eq!(
+            calculate_part_numbers(SIGNED, &symbol).unwrap(),
+            4361 + 10 + 2 + 3
+        );
+        assert_eq!(sum_gear_ratios(SIGNED, &symbol).unwrap(), 467835);
+
+        let sign = ParseOptions { minus: Minus::Sign };
+        // `-` is a sign: -617 replaces 617, the bottom `*` pairs 10 with -2,
+        // and -3 touches nothing.
+        assert_eq!(
+            calculate_part_numbers(SIGNED, &sign).unwrap(),
+            4361 - 2 * 617 + 10 - 2
+        );
+        assert_eq!(sum_gear_ratios(SIGNED, &sign).unwrap(), 467835 + 10 * -2);
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::HashSet;
+use std::ops::Range;
+
+use crate::{Arity, Schematic, SymbolClass};
+
+/// What a cell is drawn as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    PartNumber,
+    OtherNumber,
+    /// A `*` touching exactly two numbers.
+    Gear,
+    Symbol,
+    Plain,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// ANSI escape codes: part numbers green, other numbers red, gears bold
+    /// yellow and other symbols cyan.
+    Color,
+    /// Plain text with every run wrapped in a tag: `<p>` part numbers, `<n>`
+    /// other numbers, `<g>` gears and `<s>` other symbols. Cells that are
+    /// `<`, `>` or `&` are written as `&lt;`, `&gt;` and `&amp;`.
+    Markup,
+}
+
+impl Category {
+    fn open(self, style: Style) -> &'static str {
+        match (style, self) {
+            (_, Category::Plain) => "",
+            (Style::Color, Category::PartNumber) => "\x1b[32m",
+            (Style::Color, Category::OtherNumber) => "\x1b[31m",
+            (Style::Color, Category::Gear) => "\x1b[1;33m",
+            (Style::Color, Category::Symbol) => "\x1b[36m",
+            (Style::Markup, Category::PartNumber) => "<p>",
+            (Style::Markup, Category::OtherNumber) => "<n>",
+            (Style::Markup, Category::Gear) => "<g>",
+            (Style::Markup, Category::Symbol) => "<s>",
+        }
+    }
+
+    fn close(self, style: Style) -> &'static str {
+        match (style, self) {
+            (_, Category::Plain) => "",
+            (Style::Color, _) => "\x1b[0m",
+            (Style::Markup, Category::PartNumber) => "</p>",
+            (Style::Markup, Category::OtherNumber) => "</n>",
+            (Style::Markup, Category::Gear) => "</g>",
+            (Style::Markup, Category::Symbol) => "</s>",
+        }
+    }
+}
+
+/// The cells within `radius` rows and columns of `(row, col)`. Any part of
+/// it outside the grid is left out, however large `radius` is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Window {
+    pub row: usize,
+    pub col: usize,
+    pub radius: usize,
+}
+
+/// The indices within `radius` of `center`, clamped to `0..len`.
+fn span(center: usize, radius: usize, len: usize) -> Range<usize> {
+    let start = center.saturating_sub(radius).min(len);
+    let end = center.saturating_add(radius).saturating_add(1).min(len);
+    start..end
+}
+
+fn push_cell(out: &mut String, cell: &str, style: Style) {
+    if style == Style::Color {
+        out.push_str(cell);
+        return;
+    }
+    for c in cell.chars() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+pub struct Renderer<'a> {
+    schematic: &'a Schematic,
+    gears: HashSet<(usize, usize)>,
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(schematic: &'a Schematic) -> Self {
+        let gears = schematic
+            .hubs(&SymbolClass::Char('*'), Arity::Exactly(2))
+            .iter()
+            .map(|hub| (hub.row, hub.col))
+            .collect();
+        Renderer { schematic, gears }
+    }
+
+    pub fn category(&self, row: usize, col: usize) -> Category {
+        if let Some(number) = self.schematic.number_at(row, col) {
+            return if self.schematic.is_part(number) {
+                Category::PartNumber
+            } else {
+                Category::OtherNumber
+            };
+        }
+        match self.schematic.get(row, col) {
+            Some(_) if self.gears.contains(&(row, col)) => Category::Gear,
+            Some(_) if self.schematic.is_symbol_at(row, col) => Category::Symbol,
+            _ => Category::Plain,
+        }
+    }
+
+    /// Draws the schematic, or just `window` of it, one line per row. Runs of
+    /// cells in the same category share one pair of escapes or tags.
+    pub fn render(&self, style: Style, window: Option<Window>) -> String {
+        let (rows, cols) = match window {
+            Some(w) => (
+                span(w.row, w.radius, self.schematic.height()),
+                span(w.col, w.radius, self.schematic.width()),
+            ),
+            None => (0..self.schematic.height(), 0..self.schematic.width()),
+        };
+
+        let mut out = String::new();
+        for row in rows {
+            let mut current = Category::Plain;
+            for col in cols.clone() {
+                let category = self.category(row, col);
+                if category != current {
+                    out.push_str(current.close(style));
+                    out.push_str(category.open(style));
+                    current = category;
+                }
+                push_cell(&mut out, self.schematic.get(row, col).unwrap(), style);
+            }
+            out.push_str(current.close(style));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::tests::EXAMPLE;
+
+    #[test]
+    fn test_markup() {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        let rendered = Renderer::new(&schematic).render(Style::Markup, None);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[0], "<p>467</p>..<n>114</n>..");
+        assert_eq!(lines[1], "...<g>*</g>......");
+        assert_eq!(lines[4], "<p>617</p><s>*</s>......");
+        assert_eq!(lines[5], ".....<s>+</s>.<n>58</n>.");
+    }
+
+    #[test]
+    fn test_markup_escapes_cells() {
+        let schematic = Schematic::parse("1<.\n.&>").unwrap();
+        let renderer = Renderer::new(&schematic);
+        assert_eq!(
+            renderer.render(Style::Markup, None),
+            "<p>1</p><s>&lt;</s>.\n.<s>&amp;&gt;</s>\n"
+        );
+        assert_eq!(
+            renderer.render(Style::Color, None),
+            "\x1b[32m1\x1b[0m\x1b[36m<\x1b[0m.\n.\x1b[36m&>\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn test_color() {
+        let schematic = Schematic::parse("1*2\n..#").unwrap();
+        let rendered = Renderer::new(&schematic).render(Style::Color, None);
+        assert_eq!(
+            rendered,
+            "\x1b[32m1\x1b[0m\x1b[1;33m*\x1b[0m\x1b[32m2\x1b[0m\n..\x1b[36m#\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn test_window() {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        let renderer = Renderer::new(&schematic);
+        let window = Window {
+            row: 0,
+            col: 1,
+            radius: 1,
+        };
+        assert_eq!(
+            renderer.render(Style::Markup, Some(window)),
+            "<p>467</p>\n...\n"
+        );
+
+        let window = Window {
+            row: 8,
+            col: 6,
+            radius: 1,
+        };
+        assert_eq!(
+            renderer.render(Style::Markup, Some(window)),
+            ".<p>75</p>\n<g>*</g>..\n<p>598</p>\n"
+        );
+    }
+
+    #[test]
+    fn test_huge_windows_are_clamped() {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        let renderer = Renderer::new(&schematic);
+        let whole = renderer.render(Style::Markup, None);
+        for (row, col) in [(0, 0), (5, 5), (usize::MAX, usize::MAX)] {
+            let window = Window {
+                row,
+                col,
+                radius: usize::MAX,
+            };
+            assert_eq!(renderer.render(Style::Markup, Some(window)), whole);
+        }
+
+        let window = Window {
+            row: 100,
+            col: 0,
+            radius: 1,
+        };
+        assert_eq!(renderer.render(Style::Markup, Some(window)), "");
+    }
+
+    #[test]
+    fn test_span() {
+        assert_eq!(span(5, 2, 10), 3..8);
+        assert_eq!(span(1, 3, 10), 0..5);
+        assert_eq!(span(8, 3, 10), 5..10);
+        assert_eq!(span(usize::MAX, usize::MAX, 10), 0..10);
+        assert_eq!(span(20, 3, 10), 10..10);
+    }
+}
@@ -0,0 +1,683 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::io::{Error, ErrorKind};
+use std::str::FromStr;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A run of digits on one row, `start..end` in columns. With
+/// [`Minus::Sign`] the span includes a leading `-`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Number {
+    pub value: i64,
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// How a `-` directly in front of a digit is read.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Minus {
+    /// `-` is a symbol like any other, so `-12` is the part number 12 (it
+    /// touches its own `-`). This is what the puzzle intends.
+    #[default]
+    Symbol,
+    /// `-` followed by a digit is the sign of that number, so `-12` is the
+    /// number -12 spanning three cells, and its `-` is not a symbol. `5-3` is
+    /// then 5 next to -3. A `-` not followed by a digit is still a symbol.
+    Sign,
+}
+
+impl FromStr for Minus {
+    type Err = ParseMinusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "symbol" => Ok(Minus::Symbol),
+            "sign" => Ok(Minus::Sign),
+            other => Err(ParseMinusError(other.to_string())),
+        }
+    }
+}
+
+/// A `--minus` value other than `symbol` or `sign`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMinusError(String);
+
+impl fmt::Display for ParseMinusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected `symbol` or `sign`, found `{}`", self.0)
+    }
+}
+
+impl std::error::Error for ParseMinusError {}
+
+impl From<ParseMinusError> for Error {
+    fn from(err: ParseMinusError) -> Self {
+        Error::new(ErrorKind::InvalidInput, err)
+    }
+}
+
+/// A number in a schematic too long to fit in an `i64`, found at `(row,
+/// col)`, the cell it starts at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseSchematicError {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl fmt::Display for ParseSchematicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the number at row {}, column {} does not fit in an i64",
+            self.row, self.col
+        )
+    }
+}
+
+impl std::error::Error for ParseSchematicError {}
+
+impl From<ParseSchematicError> for Error {
+    fn from(err: ParseSchematicError) -> Self {
+        Error::new(ErrorKind::InvalidData, err)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    pub minus: Minus,
+}
+
+/// The engine schematic as a grid. Short rows read as if padded with `.`.
+///
+/// A column is one extended grapheme cluster, what a reader sees as one
+/// character: an `e` followed by a combining accent is a single `é` cell, and
+/// a row is as many cells wide as it has graphemes whatever its length in
+/// bytes. Numbers are made of cells that are a lone ASCII digit only.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schematic {
+    width: usize,
+    height: usize,
+    rows: Vec<Row>,
+    numbers: Vec<Number>,
+    /// For every cell, the index into `numbers` of the number covering it.
+    number_at: Vec<Option<usize>>,
+    options: ParseOptions,
+}
+
+/// One row of the grid: its text, kept whole, and where each of its cells
+/// starts. A pure ASCII row needs no offsets, since there cell `i` is byte
+/// `i`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Row {
+    text: String,
+    /// The byte offset of every cell followed by `text.len()`, or nothing
+    /// while every cell is a single byte.
+    starts: Vec<u32>,
+}
+
+impl Row {
+    /// Splits `line`, without its line ending, into cells.
+    pub(crate) fn new(line: &str) -> Self {
+        let text = line.trim_end_matches('\r').to_string();
+        let starts = if text.is_ascii() {
+            Vec::new()
+        } else {
+            text.grapheme_indices(true)
+                .map(|(start, _)| start)
+                .chain([text.len()])
+                .map(offset)
+                .collect()
+        };
+        Row { text, starts }
+    }
+
+    /// The number of cells.
+    pub(crate) fn len(&self) -> usize {
+        if self.starts.is_empty() {
+            self.text.len()
+        } else {
+            self.starts.len() - 1
+        }
+    }
+
+    pub(crate) fn cell(&self, col: usize) -> Option<&str> {
+        if self.starts.is_empty() {
+            return self.text.get(col..col + 1);
+        }
+        let start = *self.starts.get(col)? as usize;
+        let end = *self.starts.get(col + 1)? as usize;
+        Some(&self.text[start..end])
+    }
+
+    /// The numbers in this row, as row `row` of a schematic, that start in
+    /// columns `from..to`, in order. `from` must not fall inside a number;
+    /// the last one found may run past `to`.
+    pub(crate) fn numbers(
+        &self,
+        row: usize,
+        options: &ParseOptions,
+        from: usize,
+        to: usize,
+    ) -> Result<Vec<Number>, ParseSchematicError> {
+        let digit = |i: usize| self.cell(i).is_some_and(is_digit);
+        let mut numbers = Vec::new();
+        let mut col = from;
+        while col < to {
+            let signed =
+                options.minus == Minus::Sign && self.cell(col) == Some("-") && digit(col + 1);
+            if !signed && !digit(col) {
+                col += 1;
+                continue;
+            }
+            let start = col;
+            if signed {
+                col += 1;
+            }
+            // Accumulate towards the sign, so `-9223372036854775808` fits.
+            let sign = if signed { -1 } else { 1 };
+            let mut value: i64 = 0;
+            while let Some(cell) = self.cell(col).filter(|cell| is_digit(cell)) {
+                let digit = sign * (cell.as_bytes()[0] - b'0') as i64;
+                value = value
+                    .checked_mul(10)
+                    .and_then(|value| value.checked_add(digit))
+                    .ok_or(ParseSchematicError { row, col: start })?;
+                col += 1;
+            }
+            numbers.push(Number {
+                value,
+                row,
+                start,
+                end: col,
+            });
+        }
+        Ok(numbers)
+    }
+
+    /// Overwrites cell `col` with `cell`, first padding the row with `.` if
+    /// it is shorter than that.
+    fn set(&mut self, col: usize, cell: &str) {
+        while self.len() <= col {
+            self.text.push('.');
+            if !self.starts.is_empty() {
+                self.starts.push(offset(self.text.len()));
+            }
+        }
+        if self.starts.is_empty() {
+            if cell.len() == 1 {
+                self.text.replace_range(col..col + 1, cell);
+                return;
+            }
+            self.starts = (0..=self.text.len()).map(offset).collect();
+        }
+
+        let start = self.starts[col] as usize;
+        let end = self.starts[col + 1] as usize;
+        self.text.replace_range(start..end, cell);
+        for next in &mut self.starts[col + 1..] {
+            *next = offset(*next as usize - end + start + cell.len());
+        }
+    }
+}
+
+fn offset(bytes: usize) -> u32 {
+    u32::try_from(bytes).expect("a schematic row is under 4 GiB")
+}
+
+/// Whether the grapheme `cell` is anything but empty space or a digit or
+/// letter, in any script, judged by its first `char`: `é` and `٣` are not
+/// symbols, with or without combining marks, while `§` and `→` are. A `-`
+/// read as a sign also passes this test; use [`Schematic::is_symbol_at`] for
+/// cells.
+pub fn is_symbol(cell: &str) -> bool {
+    cell != "." && !cell.chars().next().is_some_and(char::is_alphanumeric)
+}
+
+/// Whether the grapheme `cell` is a digit of a number.
+fn is_digit(cell: &str) -> bool {
+    cell.len() == 1 && cell.as_bytes()[0].is_ascii_digit()
+}
+
+impl Schematic {
+    pub fn parse(input: &str) -> Result<Self, ParseSchematicError> {
+        Self::parse_with(input, &ParseOptions::default())
+    }
+
+    pub fn parse_with(input: &str, options: &ParseOptions) -> Result<Self, ParseSchematicError> {
+        Self::from_lines(input.lines(), options)
+    }
+
+    /// Builds a schematic from its rows, each without its line ending.
+    fn from_lines<'a, I>(lines: I, options: &ParseOptions) -> Result<Self, ParseSchematicError>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let rows: Vec<Row> = lines.into_iter().map(Row::new).collect();
+        let width = rows.iter().map(Row::len).max().unwrap_or(0);
+        let height = rows.len();
+
+        let mut schematic = Schematic {
+            width,
+            height,
+            rows,
+            numbers: Vec::new(),
+            number_at: vec![None; width * height],
+            options: *options,
+        };
+        for row in 0..height {
+            let numbers = schematic.rows[row].numbers(row, options, 0, width)?;
+            schematic.index(numbers);
+        }
+        Ok(schematic)
+    }
+
+    /// Adds `numbers`, which must not overlap any already indexed.
+    fn index(&mut self, numbers: Vec<Number>) {
+        for number in numbers {
+            let row = number.row;
+            let index = self.numbers.len();
+            self.numbers.push(number);
+            for c in number.start..number.end {
+                self.number_at[row * self.width + c] = Some(index);
+            }
+        }
+    }
+
+    /// The columns of `row` whose numbers can change when the cell at `col`
+    /// does: every number touching `col` or its two neighbors, and the
+    /// neighbors themselves, since a digit there can merge with a new one or
+    /// pick up a `-` in front.
+    pub(crate) fn edit_span(&self, row: usize, col: usize) -> (usize, usize) {
+        let around = col.saturating_sub(1)..(col + 2).min(self.width);
+        let (mut lo, mut hi) = (around.start, around.end);
+        for c in around {
+            if let Some(number) = self.number_at(row, c) {
+                lo = lo.min(number.start);
+                hi = hi.max(number.end);
+            }
+        }
+        (lo, hi)
+    }
+
+    /// Overwrites the cell at `(row, col)` with the single grapheme `cell`
+    /// and re-indexes only the numbers on that row that the change can
+    /// split, join, grow or shrink.
+    ///
+    /// Removed numbers are swapped out of [`Schematic::numbers`] and new ones
+    /// appended, so after an edit that list is no longer in reading order.
+    ///
+    /// An edit that would make a number too long for an `i64` is an error
+    /// and leaves the schematic as it was.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(row, col)` is outside the grid or `cell` is not exactly
+    /// one grapheme.
+    pub fn set(&mut self, row: usize, col: usize, cell: &str) -> Result<(), ParseSchematicError> {
+        assert!(
+            row < self.height && col < self.width,
+            "cell ({}, {}) is outside the {}x{} schematic",
+            row,
+            col,
+            self.height,
+            self.width
+        );
+        assert!(
+            cell.graphemes(true).count() == 1,
+            "`{}` is not a single grapheme",
+            cell
+        );
+        let (lo, hi) = self.edit_span(row, col);
+        let old = self.rows[row].cell(col).unwrap_or(".").to_string();
+        self.rows[row].set(col, cell);
+        let numbers = match self.rows[row].numbers(row, &self.options, lo, hi) {
+            Ok(numbers) => numbers,
+            Err(err) => {
+                self.rows[row].set(col, &old);
+                return Err(err);
+            }
+        };
+
+        for c in lo..hi {
+            if let Some(index) = self.number_at[row * self.width + c] {
+                self.remove_number(index);
+            }
+        }
+        self.index(numbers);
+        Ok(())
+    }
+
+    fn remove_number(&mut self, index: usize) {
+        let removed = self.numbers.swap_remove(index);
+        for c in removed.start..removed.end {
+            self.number_at[removed.row * self.width + c] = None;
+        }
+        if let Some(moved) = self.numbers.get(index) {
+            for c in moved.start..moved.end {
+                self.number_at[moved.row * self.width + c] = Some(index);
+            }
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&str> {
+        if row < self.height && col < self.width {
+            Some(self.rows[row].cell(col).unwrap_or("."))
+        } else {
+            None
+        }
+    }
+
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    /// The number covering `(row, col)`, if any.
+    pub fn number_at(&self, row: usize, col: usize) -> Option<&Number> {
+        if row < self.height && col < self.width {
+            self.number_at[row * self.width + col].map(|index| &self.numbers[index])
+        } else {
+            None
+        }
+    }
+
+    /// Whether the cell at `(row, col)` is a symbol rather than empty space,
+    /// a letter or part of a number.
+    pub fn is_symbol_at(&self, row: usize, col: usize) -> bool {
+        self.get(row, col).is_some_and(is_symbol)
+            && self.number_at[row * self.width + col].is_none()
+    }
+
+    /// Every `(row, col, symbol)` in reading order.
+    pub fn symbols(&self) -> impl Iterator<Item = (usize, usize, &str)> + '_ {
+        self.rows.iter().enumerate().flat_map(move |(row, line)| {
+            (0..line.len())
+                .filter(move |&col| self.is_symbol_at(row, col))
+                .filter_map(move |col| Some((row, col, line.cell(col)?)))
+        })
+    }
+
+    /// The distinct symbols that appear in this schematic.
+    pub fn symbol_set(&self) -> BTreeSet<&str> {
+        self.symbols().map(|(_, _, cell)| cell).collect()
+    }
+
+    /// The in-bounds cells touching `number`, diagonals included.
+    pub fn neighbors(&self, number: &Number) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let rows = number.row.saturating_sub(1)..(number.row + 2).min(self.height);
+        let cols = number.start.saturating_sub(1)..(number.end + 1).min(self.width);
+        let number = *number;
+        rows.flat_map(move |row| cols.clone().map(move |col| (row, col)))
+            .filter(move |&(row, col)| row != number.row || col < number.start || col >= number.end)
+    }
+
+    /// The distinct numbers touching the cell at `(row, col)`, in reading
+    /// order.
+    pub fn numbers_around(&self, row: usize, col: usize) -> Vec<&Number> {
+        self.number_indices_around(row, col)
+            .into_iter()
+            .map(|index| &self.numbers[index])
+            .collect()
+    }
+
+    /// Like [`Schematic::numbers_around`], but as indices into
+    /// [`Schematic::numbers`].
+    pub fn number_indices_around(&self, row: usize, col: usize) -> Vec<usize> {
+        let mut found: Vec<usize> = Vec::new();
+        for r in row.saturating_sub(1)..(row + 2).min(self.height) {
+            for c in col.saturating_sub(1)..(col + 2).min(self.width) {
+                if let Some(index) = self.number_at[r * self.width + c] {
+                    if !found.contains(&index) {
+                        found.push(index);
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    /// A part number is any number touching a symbol.
+    pub fn is_part(&self, number: &Number) -> bool {
+        self.neighbors(number)
+            .any(|(row, col)| self.is_symbol_at(row, col))
+    }
+
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> + '_ {
+        self.numbers.iter().filter(|number| self.is_part(number))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub const EXAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn test_numbers() {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        assert_eq!(schematic.numbers().len(), 10);
+        assert_eq!(
+            schematic.numbers()[0],
+            Number {
+                value: 467,
+                row: 0,
+                start: 0,
+                end: 3
+            }
+        );
+        assert_eq!(schematic.number_at(2, 7).map(|n| n.value), Some(633));
+        assert_eq!(schematic.number_at(2, 5), None);
+    }
+
+    #[test]
+    fn test_symbol_set() {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        let symbols: String = schematic.symbol_set().into_iter().collect();
+        assert_eq!(symbols, "#$*+");
+    }
+
+    #[test]
+    fn test_neighbors_at_edges() {
+        let schematic = Schematic::parse("12.\n..*").unwrap();
+        let number = schematic.numbers()[0];
+        let neighbors: Vec<(usize, usize)> = schematic.neighbors(&number).collect();
+        assert_eq!(neighbors, vec![(0, 2), (1, 0), (1, 1), (1, 2)]);
+        assert!(schematic.is_part(&number));
+    }
+
+    #[test]
+    fn test_part_numbers() {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        let non_parts: Vec<i64> = schematic
+            .numbers()
+            .iter()
+            .filter(|number| !schematic.is_part(number))
+            .map(|number| number.value)
+            .collect();
+        assert_eq!(non_parts, vec![114, 58]);
+    }
+
+    #[test]
+    fn test_parse_minus() {
+        assert_eq!("symbol".parse(), Ok(Minus::Symbol));
+        assert_eq!(" sign".parse(), Ok(Minus::Sign));
+        let err = "dash".parse::<Minus>().unwrap_err();
+        assert_eq!(err.to_string(), "expected `symbol` or `sign`, found `dash`");
+    }
+
+    #[test]
+    fn test_minus_as_symbol() {
+        let schematic = Schematic::parse("-12..5-3\n..-.....").unwrap();
+        let numbers: Vec<(i64, usize, usize)> = schematic
+            .numbers()
+            .iter()
+            .map(|n| (n.value, n.start, n.end))
+            .collect();
+        assert_eq!(numbers, vec![(12, 1, 3), (5, 5, 6), (3, 7, 8)]);
+        assert_eq!(schematic.symbols().count(), 3);
+        let parts: Vec<i64> = schematic.part_numbers().map(|n| n.value).collect();
+        assert_eq!(parts, vec![12, 5, 3]);
+    }
+
+    #[test]
+    fn test_minus_as_sign() {
+        let options = ParseOptions { minus: Minus::Sign };
+        let schematic = Schematic::parse_with("-12..5-3\n..-.....\n.-......", &options).unwrap();
+        let numbers: Vec<(i64, usize, usize)> = schematic
+            .numbers()
+            .iter()
+            .map(|n| (n.value, n.start, n.end))
+            .collect();
+        assert_eq!(numbers, vec![(-12, 0, 3), (5, 5, 6), (-3, 6, 8)]);
+
+        // Only the lone `-` on the second row is a symbol; the one on the
+        // third row is too far away to matter.
+        let symbols: Vec<(usize, usize, &str)> = schematic.symbols().collect();
+        assert_eq!(symbols, vec![(1, 2, "-"), (2, 1, "-")]);
+        assert!(!schematic.is_symbol_at(0, 0));
+        assert!(!schematic.is_symbol_at(0, 6));
+        assert_eq!(schematic.number_at(0, 0).map(|n| n.value), Some(-12));
+
+        let parts: Vec<i64> = schematic.part_numbers().map(|n| n.value).collect();
+        assert_eq!(parts, vec![-12]);
+    }
+
+    #[test]
+    fn test_ragged_rows_and_unlisted_symbols() {
+        let schematic = Schematic::parse("1\n~22\n..3\n").unwrap();
+        assert_eq!(schematic.width(), 3);
+        assert_eq!(schematic.get(0, 2), Some("."));
+        let parts: Vec<i64> = schematic.part_numbers().map(|n| n.value).collect();
+        assert_eq!(parts, vec![1, 22]);
+    }
+
+    #[test]
+    fn test_non_ascii_cells() {
+        let schematic = Schematic::parse("ü12..\n.§..3\n→...é\n٣4..7").unwrap();
+        assert_eq!(schematic.width(), 5);
+        assert_eq!(schematic.get(1, 1), Some("§"));
+        assert_eq!(schematic.get(2, 4), Some("é"));
+
+        let numbers: Vec<(i64, usize, usize, usize)> = schematic
+            .numbers()
+            .iter()
+            .map(|n| (n.value, n.row, n.start, n.end))
+            .collect();
+        assert_eq!(
+            numbers,
+            vec![(12, 0, 1, 3), (3, 1, 4, 5), (4, 3, 1, 2), (7, 3, 4, 5)]
+        );
+
+        // Letters and digits from other scripts are not symbols.
+        let symbols: String = schematic.symbol_set().into_iter().collect();
+        assert_eq!(symbols, "§→");
+        let parts: Vec<i64> = schematic.part_numbers().map(|n| n.value).collect();
+        assert_eq!(parts, vec![12, 4]);
+    }
+
+    #[test]
+    fn test_combining_sequences_are_one_cell() {
+        // A decomposed `e\u{301}` is a letter in one column, so 7 after it
+        // sits in column 3. A combining mark keeps a symbol a symbol, but
+        // takes a digit out of its number without making it a symbol.
+        let schematic = Schematic::parse("4e\u{301}.7\n§\u{301}...\n..1\u{20e3}5").unwrap();
+        assert_eq!(schematic.width(), 4);
+        assert_eq!(schematic.get(0, 1), Some("e\u{301}"));
+        assert_eq!(schematic.get(1, 0), Some("§\u{301}"));
+        assert_eq!(schematic.get(2, 2), Some("1\u{20e3}"));
+
+        let symbols: Vec<(usize, usize, &str)> = schematic.symbols().collect();
+        assert_eq!(symbols, vec![(1, 0, "§\u{301}")]);
+        let numbers: Vec<(i64, usize, usize)> = schematic
+            .numbers()
+            .iter()
+            .map(|n| (n.value, n.row, n.start))
+            .collect();
+        assert_eq!(numbers, vec![(4, 0, 0), (7, 0, 3), (5, 2, 3)]);
+        let parts: Vec<i64> = schematic.part_numbers().map(|n| n.value).collect();
+        assert_eq!(parts, vec![4]);
+    }
+
+    #[test]
+    fn test_row_cells() {
+        let mut row = Row::new("4.*\r");
+        assert!(row.starts.is_empty());
+        assert_eq!((row.len(), row.cell(2), row.cell(3)), (3, Some("*"), None));
+        row.set(1, "#");
+        assert_eq!(row.text, "4#*");
+        assert!(row.starts.is_empty());
+
+        // A wider cell switches the row to offsets; later cells move along.
+        row.set(1, "e\u{301}");
+        row.set(4, "5");
+        assert_eq!(row.text, "4e\u{301}*.5");
+        let cells: Vec<&str> = (0..row.len()).filter_map(|col| row.cell(col)).collect();
+        assert_eq!(cells, vec!["4", "e\u{301}", "*", ".", "5"]);
+        row.set(1, ".");
+        assert_eq!(row.text, "4.*.5");
+        assert_eq!(row.cell(4), Some("5"));
+
+        let row = Row::new("§1\u{20e3}.");
+        let cells: Vec<&str> = (0..row.len()).filter_map(|col| row.cell(col)).collect();
+        assert_eq!(cells, vec!["§", "1\u{20e3}", "."]);
+    }
+
+    #[test]
+    fn test_set_grapheme() {
+        let mut schematic = Schematic::parse("1..\n...").unwrap();
+        schematic.set(1, 1, "#\u{301}").unwrap();
+        assert_eq!(schematic.get(1, 1), Some("#\u{301}"));
+        assert!(schematic.is_part(&schematic.numbers()[0]));
+    }
+
+    #[test]
+    fn test_overflowing_numbers() {
+        let err = Schematic::parse("..\n.12345678901234567890*").unwrap_err();
+        assert_eq!(err, ParseSchematicError { row: 1, col: 1 });
+        assert_eq!(
+            err.to_string(),
+            "the number at row 1, column 1 does not fit in an i64"
+        );
+
+        let sign = ParseOptions { minus: Minus::Sign };
+        let schematic = Schematic::parse_with("-9223372036854775808*", &sign).unwrap();
+        assert_eq!(schematic.numbers()[0].value, i64::MIN);
+        assert!(Schematic::parse("9223372036854775808*").is_err());
+    }
+
+    #[test]
+    fn test_overflowing_set_changes_nothing() {
+        let mut schematic = Schematic::parse("9223372036.854775807*").unwrap();
+        let before = schematic.clone();
+        assert_eq!(
+            schematic.set(0, 10, "0"),
+            Err(ParseSchematicError { row: 0, col: 0 })
+        );
+        assert_eq!(schematic, before);
+    }
+
+    #[test]
+    #[should_panic(expected = "not a single grapheme")]
+    fn test_set_rejects_several_graphemes() {
+        Schematic::parse("1..").unwrap().set(0, 1, "**").unwrap();
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::VecDeque;
+use std::io::prelude::*;
+use std::io::Error;
+
+use crate::schematic::Row;
+use crate::{is_symbol, Number, ParseOptions, ParseSchematicError};
+
+/// A part number or gear found by [`stream_schematic`], with rows counted
+/// from the top of the whole input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Part(Number),
+    Gear { row: usize, col: usize, ratio: i128 },
+}
+
+/// Running part 1 and part 2 answers over every row finished so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Totals {
+    pub part_sum: i128,
+    pub gear_sum: i128,
+}
+
+/// Solves both parts while holding at most three rows of `reader` at once.
+///
+/// Whether a number is a part, or a `*` a gear, only depends on the row it
+/// sits on and the rows directly above and below. So a row is finished as
+/// soon as the one after it arrives: its part numbers go to `on_event` in
+/// column order, then its gears. Each row is split into cells and numbers
+/// once, when it is read. The answers match [`crate::calculate_part_numbers`]
+/// and [`crate::sum_gear_ratios`] on the same input.
+pub fn stream_schematic<R, F>(
+    reader: R,
+    options: &ParseOptions,
+    mut on_event: F,
+) -> Result<Totals, Error>
+where
+    R: BufRead,
+    F: FnMut(Event) -> Result<(), Error>,
+{
+    let mut window: VecDeque<Line> = VecDeque::with_capacity(3);
+    let mut totals = Totals::default();
+    let mut rows = 0;
+    for line in reader.lines() {
+        if window.len() == 3 {
+            window.pop_front();
+        }
+        window.push_back(Line::new(&line?, rows, options)?);
+        rows += 1;
+        if rows >= 2 {
+            finish_row(&window, window.len() - 2, &mut totals, &mut on_event)?;
+        }
+    }
+    if rows >= 1 {
+        finish_row(&window, window.len() - 1, &mut totals, &mut on_event)?;
+    }
+    Ok(totals)
+}
+
+/// One row of the window with the numbers on it, rows counted from the top
+/// of the whole input.
+struct Line {
+    row: usize,
+    cells: Row,
+    numbers: Vec<Number>,
+}
+
+impl Line {
+    fn new(text: &str, row: usize, options: &ParseOptions) -> Result<Self, ParseSchematicError> {
+        let cells = Row::new(text);
+        let numbers = cells.numbers(row, options, 0, cells.len())?;
+        Ok(Line {
+            row,
+            cells,
+            numbers,
+        })
+    }
+
+    /// The numbers covering any of the columns `col - 1..=col + 1`.
+    fn numbers_around(&self, col: usize) -> impl Iterator<Item = &Number> {
+        let first = self.numbers.partition_point(|number| number.end < col);
+        self.numbers[first..]
+            .iter()
+            .take_while(move |number| number.start <= col + 1)
+    }
+
+    /// Whether the cell at `col` is a symbol rather than empty space, a
+    /// letter or part of a number, as [`crate::Schematic::is_symbol_at`].
+    fn is_symbol_at(&self, col: usize) -> bool {
+        self.cells.cell(col).is_some_and(is_symbol)
+            && !self
+                .numbers_around(col)
+                .any(|number| (number.start..number.end).contains(&col))
+    }
+}
+
+/// Reports the part numbers and gears on `window[focus]`.
+fn finish_row<F>(
+    window: &VecDeque<Line>,
+    focus: usize,
+    totals: &mut Totals,
+    on_event: &mut F,
+) -> Result<(), Error>
+where
+    F: FnMut(Event) -> Result<(), Error>,
+{
+    let line = &window[focus];
+    let near = || window.range(focus.saturating_sub(1)..(focus + 2).min(window.len()));
+
+    for number in &line.numbers {
+        let cols = number.start.saturating_sub(1)..number.end + 1;
+        if near().any(|other| cols.clone().any(|col| other.is_symbol_at(col))) {
+            totals.part_sum += number.value as i128;
+            on_event(Event::Part(*number))?;
+        }
+    }
+    for col in 0..line.cells.len() {
+        if line.cells.cell(col) != Some("*") {
+            continue;
+        }
+        let numbers: Vec<&Number> = near().flat_map(|other| other.numbers_around(col)).collect();
+        if let [first, second] = numbers[..] {
+            let ratio = first.value as i128 * second.value as i128;
+            totals.gear_sum += ratio;
+            on_event(Event::Gear {
+                row: line.row,
+                col,
+                ratio,
+            })?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::tests::EXAMPLE;
+    use crate::{calculate_part_numbers, sum_gear_ratios, Minus, Schematic};
+    use aoc_testing::Lcg;
+
+    fn collect(input: &str, options: &ParseOptions) -> (Vec<Event>, Totals) {
+        let mut events = Vec::new();
+        let totals = stream_schematic(input.as_bytes(), options, |event| {
+            events.push(event);
+            Ok(())
+        })
+        .unwrap();
+        (events, totals)
+    }
+
+    #[test]
+    fn test_stream_example() {
+        let (events, totals) = collect(EXAMPLE, &ParseOptions::default());
+        assert_eq!(
+            totals,
+            Totals {
+                part_sum: 4361,
+                gear_sum: 467835
+            }
+        );
+
+        let parts: Vec<i64> = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Part(number) => Some(number.value),
+                Event::Gear { .. } => None,
+            })
+            .collect();
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        let expected: Vec<i64> = schematic.part_numbers().map(|n| n.value).collect();
+        assert_eq!(parts, expected);
+
+        assert_eq!(
+            events[1],
+            Event::Gear {
+                row: 1,
+                col: 3,
+                ratio: 467 * 35
+            }
+        );
+        assert_eq!(
+            events.last(),
+            Some(&Event::Part(Number {
+                value: 598,
+                row: 9,
+                start: 5,
+                end: 8
+            }))
+        );
+    }
+
+    #[test]
+    fn test_large_gear_ratio() {
+        let (events, totals) = collect("9223372036854775807\n*\n2", &ParseOptions::default());
+        let ratio = i64::MAX as i128 * 2;
+        assert_eq!(totals.gear_sum, ratio);
+        assert!(events.contains(&Event::Gear {
+            row: 1,
+            col: 0,
+            ratio
+        }));
+    }
+
+    #[test]
+    fn test_overflow_is_invalid_data() {
+        let input = "1*\n12345678901234567890\n";
+        let err =
+            stream_schematic(input.as_bytes(), &ParseOptions::default(), |_| Ok(())).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("row 1, column 0"));
+    }
+
+    #[test]
+    fn test_short_inputs() {
+        assert_eq!(collect("", &ParseOptions::default()).1, Totals::default());
+        let (events, totals) = collect("12*3", &ParseOptions::default());
+        assert_eq!(events.len(), 3);
+        assert_eq!(totals.gear_sum, 36);
+        let (_, totals) = collect("1\n*\n2", &ParseOptions::default());
+        assert_eq!((totals.part_sum, totals.gear_sum), (3, 2));
+    }
+
+    #[test]
+    fn test_random_boards_match_whole_board() {
+        let palette: Vec<char> = "........0123456789*-#".chars().collect();
+        let mut rng = Lcg::new(41);
+        for _ in 0..200 {
+            let rows = 1 + rng.below(12);
+            let board: Vec<String> = (0..rows)
+                .map(|_| {
+                    (0..rng.below(10))
+                        .map(|_| palette[rng.below(palette.len())])
+                        .collect()
+                })
+                .collect();
+            let board = board.join("\n");
+            for minus in [Minus::Symbol, Minus::Sign] {
+                let options = ParseOptions { minus };
+                let (_, totals) = collect(&board, &options);
+                assert_eq!(
+                    (totals.part_sum, totals.gear_sum),
+                    (
+                        calculate_part_numbers(&board, &options).unwrap(),
+                        sum_gear_ratios(&board, &options).unwrap()
+                    ),
+                    "{}",
+                    board
+                );
+            }
+        }
+    }
+}
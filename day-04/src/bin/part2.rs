@@ -1,140 +1,53 @@
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::prelude::*;
-use std::io::Error;
-
-fn main() {
-    let path = "src/day-04.txt";
-    let input = open_file(path).unwrap();
-    let total_scratchcards = calculate_total_scratchcards(&input);
-    println!("{}", total_scratchcards);
-}
-
-fn calculate_total_scratchcards(input: &str) -> usize {
-    let card_map = create_card_map(&input);
-
-    card_map.values().sum()
-}
-
-fn open_file(path: &str) -> Result<String, Error> {
-    let mut input_file = File::open(path)?;
-    let mut input_string = String::new();
-    input_file.read_to_string(&mut input_string)?;
-    Ok(input_string)
-}
-
-fn parse_line(line: &str) -> (usize, Vec<usize>, Vec<usize>) {
-    let (card_number, values) = line.split_once(":").unwrap();
-    let card_number = card_number
-        .split_whitespace()
-        .last()
-        .unwrap()
-        .parse::<usize>()
-        .unwrap();
-    let (winners, ours) = values.split_once("|").unwrap();
-
-    let winners_vec: Vec<usize> = winners
-        .split_whitespace()
-        .map(|x| x.parse::<usize>().unwrap())
-        .collect();
-    let ours_vec: Vec<usize> = ours
-        .split_whitespace()
-        .map(|x| x.parse::<usize>().unwrap())
-        .collect();
-    (card_number, winners_vec, ours_vec)
-}
-
-fn get_line_total(line_pair: (usize, Vec<usize>, Vec<usize>)) -> usize {
-    let (_card_number, winners_vec, ours_vec) = line_pair;
-    let mut count = 0;
-
-    for winner in &winners_vec {
-        if ours_vec.contains(winner) {
-            count += 1;
+use std::env;
+use std::io::{Error, ErrorKind};
+
+use day_04::{parse_cards, total_scratchcards, validate, Trace};
+
+/// Usage: `part2 [--trace table|dot] [input]`
+///
+/// `--trace` prints where every card's copies came from instead of the
+/// total, as a table or as a Graphviz graph.
+fn main() -> Result<(), Error> {
+    let mut trace = None;
+    let positional = aoc_cli::parse(env::args().skip(1), |values| {
+        match values.flag() {
+            "--trace" => trace = Some(values.value()?),
+            _ => return Ok(false),
         }
+        Ok(true)
+    })?;
+    let path = aoc_cli::input_path(positional, "src/day-04.txt")?;
+
+    let input = aoc_cli::read_input(&path)?;
+    let cards = parse_cards(&input)?;
+    for issue in validate(&cards) {
+        eprintln!("warning: {}", issue);
     }
-
-    count
-}
-
-fn create_card_map(input: &str) -> HashMap<usize, usize> {
-    let mut card_counts: HashMap<usize, usize> = HashMap::new();
-
-    let num_matches: Vec<usize> = input.lines().map(parse_line).map(get_line_total).collect();
-
-    for (row, look_ahead) in num_matches.iter().enumerate() {
-        card_counts
-            .entry(row)
-            .and_modify(|counter| *counter += 1)
-            .or_insert(1);
-
-        let count = match card_counts.get(&row) {
-            Some(num) => *num,
-            None => 1,
-        };
-        for _ in 0..count {
-            for i in 1..=*look_ahead {
-                card_counts
-                    .entry(row + i)
-                    .and_modify(|counter| *counter += 1)
-                    .or_insert(1);
+    let overflow = || Error::new(ErrorKind::InvalidData, "scratchcard total overflows u128");
+
+    if let Some(format) = trace {
+        let trace = Trace::new(&cards).ok_or_else(overflow)?;
+        match format.as_str() {
+            "table" => print!("{}", trace),
+            "dot" => print!("{}", trace.to_dot()),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("unknown trace format `{}`", format),
+                ))
             }
         }
+        return Ok(());
     }
 
-    card_counts
+    let total_scratchcards = total_scratchcards(&cards).ok_or_else(overflow)?;
+    println!("{}", total_scratchcards);
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    #[test]
-    fn test_parse_line() {
-        assert_eq!(
-            parse_line("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"),
-            (
-                1,
-                vec![41, 48, 83, 86, 17],
-                vec![83, 86, 6, 31, 17, 9, 48, 53]
-            )
-        );
-        assert_eq!(
-            parse_line("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19"),
-            (
-                2,
-                vec![13, 32, 20, 16, 61],
-                vec![61, 30, 68, 82, 17, 32, 24, 19]
-            )
-        );
-    }
-
-    #[test]
-    fn test_get_line_total() {
-        assert_eq!(
-            get_line_total((
-                1,
-                vec![41, 48, 83, 86, 17],
-                vec![83, 86, 6, 31, 17, 9, 48, 53]
-            )),
-            4
-        );
-        assert_eq!(
-            get_line_total((
-                2,
-                vec![13, 32, 20, 16, 61],
-                vec![61, 30, 68, 82, 17, 32, 24, 19]
-            )),
-            2
-        );
-        assert_eq!(
-            get_line_total((
-                5,
-                vec![87, 83, 26, 28, 32],
-                vec![88, 30, 70, 12, 93, 22, 82, 36]
-            )),
-            0
-        );
-    }
 
     #[test]
     fn test_calculate_total_scratchcards() {
@@ -145,6 +58,7 @@ mod tests {
     Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
     Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
 
-        assert_eq!(calculate_total_scratchcards(&input), 30);
+        let cards = parse_cards(input).unwrap();
+        assert_eq!(total_scratchcards(&cards), Some(30));
     }
 }
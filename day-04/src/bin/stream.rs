@@ -0,0 +1,20 @@
+use std::env;
+use std::io::Error;
+
+use day_04::{stream_cards, Card};
+
+/// Usage: `stream [input]`
+///
+/// Reads cards from `input` (stdin when missing or `-`) and prints the copies
+/// of each card and the running part 2 total as soon as the card arrives.
+fn main() -> Result<(), Error> {
+    let positional = aoc_cli::parse(env::args().skip(1), |_| Ok(false))?;
+    let path = aoc_cli::input_path(positional, "-")?;
+
+    let print = |card: &Card, copies: u128, total: u128| {
+        println!("card {}: copies {} total {}", card.id, copies, total);
+        Ok(())
+    };
+    stream_cards(aoc_cli::open_reader(&path)?, print)?;
+    Ok(())
+}
@@ -0,0 +1,173 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::io::{Error, ErrorKind};
+use std::str::FromStr;
+
+/// One scratchcard: `Card <id>: <winning numbers> | <numbers you have>`.
+/// Both lists are kept as written, duplicates included, so
+/// [`crate::validate`] can point them out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    pub id: usize,
+    pub winning: Vec<usize>,
+    pub numbers: Vec<usize>,
+}
+
+impl Card {
+    /// How many distinct winning numbers appear among the numbers you have.
+    pub fn matches(&self) -> usize {
+        let winning: HashSet<usize> = self.winning.iter().copied().collect();
+        let numbers: HashSet<usize> = self.numbers.iter().copied().collect();
+        winning.intersection(&numbers).count()
+    }
+}
+
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (header, lists) = s
+            .split_once(':')
+            .ok_or_else(|| ParseCardError::MissingColon(s.trim().to_string()))?;
+
+        let id = match header.split_whitespace().collect::<Vec<&str>>()[..] {
+            ["Card", id] => id
+                .parse::<usize>()
+                .map_err(|_| ParseCardError::InvalidId(id.to_string()))?,
+            _ => return Err(ParseCardError::MalformedHeader(header.trim().to_string())),
+        };
+
+        let (winning, numbers) = lists
+            .split_once('|')
+            .ok_or_else(|| ParseCardError::MissingBar(lists.trim().to_string()))?;
+
+        Ok(Card {
+            id,
+            winning: parse_numbers(winning)?,
+            numbers: parse_numbers(numbers)?,
+        })
+    }
+}
+
+fn parse_numbers(list: &str) -> Result<Vec<usize>, ParseCardError> {
+    list.split_whitespace()
+        .map(|number| {
+            number
+                .parse::<usize>()
+                .map_err(|_| ParseCardError::InvalidNumber(number.to_string()))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCardError {
+    MissingColon(String),
+    MalformedHeader(String),
+    InvalidId(String),
+    MissingBar(String),
+    InvalidNumber(String),
+}
+
+impl fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseCardError::MissingColon(line) => write!(f, "missing `:` in `{}`", line),
+            ParseCardError::MalformedHeader(header) => {
+                write!(f, "expected `Card <id>`, found `{}`", header)
+            }
+            ParseCardError::InvalidId(id) => write!(f, "invalid card id `{}`", id),
+            ParseCardError::MissingBar(lists) => {
+                write!(f, "expected `<winning> | <numbers>`, found `{}`", lists)
+            }
+            ParseCardError::InvalidNumber(number) => write!(f, "invalid number `{}`", number),
+        }
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+/// Parses one card per non-empty line, reporting the first bad line.
+pub fn parse_cards(input: &str) -> Result<Vec<Card>, Error> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(row, line)| {
+            line.parse::<Card>().map_err(|err| {
+                Error::new(ErrorKind::InvalidData, format!("line {}: {}", row + 1, err))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn test_parse_card() {
+        assert_eq!(
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53".parse::<Card>(),
+            Ok(Card {
+                id: 1,
+                winning: vec![41, 48, 83, 86, 17],
+                numbers: vec![83, 86, 6, 31, 17, 9, 48, 53]
+            })
+        );
+        assert_eq!(
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19".parse::<Card>(),
+            Ok(Card {
+                id: 2,
+                winning: vec![13, 32, 20, 16, 61],
+                numbers: vec![61, 30, 68, 82, 17, 32, 24, 19]
+            })
+        );
+    }
+
+    #[test]
+    fn test_matches() {
+        let cards = parse_cards(EXAMPLE).unwrap();
+        let matches: Vec<usize> = cards.iter().map(Card::matches).collect();
+        assert_eq!(matches, vec![4, 2, 2, 1, 0, 0]);
+
+        // A repeated number only matches once.
+        let card = "Card 1: 5 5 7 | 5 5 5".parse::<Card>().unwrap();
+        assert_eq!(card.matches(), 1);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "Card 1 41 | 41".parse::<Card>(),
+            Err(ParseCardError::MissingColon("Card 1 41 | 41".to_string()))
+        );
+        assert_eq!(
+            "Game 1: 41 | 41".parse::<Card>(),
+            Err(ParseCardError::MalformedHeader("Game 1".to_string()))
+        );
+        assert_eq!(
+            "Card one: 41 | 41".parse::<Card>(),
+            Err(ParseCardError::InvalidId("one".to_string()))
+        );
+        assert_eq!(
+            "Card 1: 41 41".parse::<Card>(),
+            Err(ParseCardError::MissingBar("41 41".to_string()))
+        );
+        assert_eq!(
+            "Card 1: 41 | 4x".parse::<Card>(),
+            Err(ParseCardError::InvalidNumber("4x".to_string()))
+        );
+
+        let cards = parse_cards("Card 1: 1 | 1\n\nCard 2: 1 |").unwrap();
+        assert_eq!(cards[1].numbers, Vec::<usize>::new());
+        let err = parse_cards("Card 1: 1 | 1\n\nCard 2 1 | 1").unwrap_err();
+        assert_eq!(err.to_string(), "line 3: missing `:` in `Card 2 1 | 1`");
+    }
+}
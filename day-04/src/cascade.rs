@@ -0,0 +1,231 @@
+use std::fmt;
+
+use crate::Card;
+
+/// How many copies of each card end up held, in table order, or `None` if a
+/// count does not fit in a `u128`.
+///
+/// Every copy of a card wins one copy of each of the next `matches` cards,
+/// so instead of replaying copies one by one, a single pass adds a card's
+/// whole count to the cards after it. Wins past the last card are dropped.
+pub fn card_counts(cards: &[Card]) -> Option<Vec<u128>> {
+    let mut counts: Vec<u128> = vec![1; cards.len()];
+
+    for (row, card) in cards.iter().enumerate() {
+        let count = counts[row];
+        let last = (row + card.matches()).min(cards.len() - 1);
+        for counter in &mut counts[row + 1..=last] {
+            *counter = counter.checked_add(count)?;
+        }
+    }
+
+    Some(counts)
+}
+
+/// Total number of scratchcards held at the end (part 2), or `None` if it
+/// does not fit in a `u128`.
+pub fn total_scratchcards(cards: &[Card]) -> Option<u128> {
+    card_counts(cards)?
+        .iter()
+        .try_fold(0u128, |total, &count| total.checked_add(count))
+}
+
+/// Where the copies of one card came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceRow {
+    pub id: usize,
+    pub matches: usize,
+    /// Copies held at the end, the original included.
+    pub copies: u128,
+    /// `(source card id, copies won from it)`, in table order. The counts
+    /// plus the original add up to `copies`.
+    pub won_from: Vec<(usize, u128)>,
+}
+
+/// The part 2 cascade card by card. Displays as a table; see
+/// [`Trace::to_dot`] for a graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    pub rows: Vec<TraceRow>,
+}
+
+impl Trace {
+    /// Traces the cascade over `cards`, or `None` if a count or the total
+    /// overflows.
+    pub fn new(cards: &[Card]) -> Option<Self> {
+        let counts = card_counts(cards)?;
+        counts
+            .iter()
+            .try_fold(0u128, |total, &count| total.checked_add(count))?;
+        let mut rows: Vec<TraceRow> = cards
+            .iter()
+            .zip(&counts)
+            .map(|(card, &copies)| TraceRow {
+                id: card.id,
+                matches: card.matches(),
+                copies,
+                won_from: Vec::new(),
+            })
+            .collect();
+
+        for row in 0..rows.len() {
+            let (id, last) = (rows[row].id, (row + rows[row].matches).min(rows.len() - 1));
+            for target in &mut rows[row + 1..=last] {
+                target.won_from.push((id, counts[row]));
+            }
+        }
+        Some(Trace { rows })
+    }
+
+    pub fn total(&self) -> u128 {
+        self.rows.iter().map(|row| row.copies).sum()
+    }
+
+    /// A Graphviz digraph with one node per card, labelled with its copies,
+    /// and an edge from each card to every card it won copies of, labelled
+    /// with how many.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph cascade {\n");
+        for row in &self.rows {
+            out.push_str(&format!(
+                "    card{} [label=\"card {}\\n{} {}\"];\n",
+                row.id,
+                row.id,
+                row.copies,
+                if row.copies == 1 { "copy" } else { "copies" }
+            ));
+        }
+        for row in &self.rows {
+            for (source, copies) in &row.won_from {
+                out.push_str(&format!(
+                    "    card{} -> card{} [label=\"{}\"];\n",
+                    source, row.id, copies
+                ));
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>6}  {:>7}  {:>8}  sources",
+            "card", "matches", "copies"
+        )?;
+        for row in &self.rows {
+            let mut sources = vec![String::from("1 original")];
+            sources.extend(
+                row.won_from
+                    .iter()
+                    .map(|(source, copies)| format!("{} from card {}", copies, source)),
+            );
+            writeln!(
+                f,
+                "{:>6}  {:>7}  {:>8}  {}",
+                row.id,
+                row.matches,
+                row.copies,
+                sources.join(", ")
+            )?;
+        }
+        writeln!(f, "{:>6}  {:>7}  {:>8}", "total", "", self.total())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::tests::EXAMPLE;
+    use crate::parse_cards;
+
+    /// Card `i` of `n` matches every card after it, so it ends up with
+    /// `2^i` copies and the table holds `2^n - 1` cards in total.
+    fn doubling_table(n: usize) -> Vec<Card> {
+        (0..n)
+            .map(|i| {
+                let numbers: Vec<usize> = (1..n - i).collect();
+                Card {
+                    id: i + 1,
+                    winning: numbers.clone(),
+                    numbers,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_example_counts() {
+        let cards = parse_cards(EXAMPLE).unwrap();
+        assert_eq!(card_counts(&cards), Some(vec![1, 2, 4, 8, 14, 1]));
+        assert_eq!(total_scratchcards(&cards), Some(30));
+    }
+
+    #[test]
+    fn test_exponential_copies() {
+        let cards = doubling_table(100);
+        let counts = card_counts(&cards).unwrap();
+        assert_eq!(counts[99], 1 << 99);
+        assert_eq!(total_scratchcards(&cards), Some((1 << 100) - 1));
+
+        assert_eq!(total_scratchcards(&doubling_table(128)), Some(u128::MAX));
+        assert_eq!(total_scratchcards(&doubling_table(129)), None);
+    }
+
+    #[test]
+    fn test_wins_past_the_end_are_dropped() {
+        let cards = parse_cards(
+            "Card 1: 1 2 | 1 2
+Card 2: 3 | 4",
+        )
+        .unwrap();
+        assert_eq!(card_counts(&cards), Some(vec![1, 2]));
+    }
+
+    #[test]
+    fn test_trace() {
+        let trace = Trace::new(&parse_cards(EXAMPLE).unwrap()).unwrap();
+        assert_eq!(trace.total(), 30);
+        assert_eq!(
+            trace.rows[4],
+            TraceRow {
+                id: 5,
+                matches: 0,
+                copies: 14,
+                won_from: vec![(1, 1), (3, 4), (4, 8)]
+            }
+        );
+        for row in &trace.rows {
+            let won: u128 = row.won_from.iter().map(|(_, copies)| copies).sum();
+            assert_eq!(row.copies, 1 + won);
+        }
+
+        let table = trace.to_string();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "  card  matches    copies  sources");
+        assert_eq!(
+            lines[3],
+            "     3        2         4  1 original, 1 from card 1, 2 from card 2"
+        );
+        assert_eq!(lines[7], " total                 30");
+    }
+
+    #[test]
+    fn test_dot() {
+        let cards = parse_cards("Card 1: 1 2 | 1 2\nCard 2: 1 | 1\nCard 3: 1 | 2").unwrap();
+        assert_eq!(
+            Trace::new(&cards).unwrap().to_dot(),
+            "digraph cascade {
+    card1 [label=\"card 1\\n1 copy\"];
+    card2 [label=\"card 2\\n2 copies\"];
+    card3 [label=\"card 3\\n4 copies\"];
+    card1 -> card2 [label=\"1\"];
+    card1 -> card3 [label=\"1\"];
+    card2 -> card3 [label=\"2\"];
+}
+"
+        );
+    }
+}
@@ -0,0 +1,11 @@
+mod card;
+mod cascade;
+mod scoring;
+mod stream;
+mod validate;
+
+pub use card::{parse_cards, Card, ParseCardError};
+pub use cascade::{card_counts, total_scratchcards, Trace, TraceRow};
+pub use scoring::{total_points, Expr, Score, Scoring, ScoringError};
+pub use stream::{stream_cards, Cascade};
+pub use validate::{validate, Issue};
@@ -0,0 +1,410 @@
+//! Rules turning a card's match count into points for part 1.
+//!
+//! A rule is chosen by name: `doubling` (the puzzle's), `linear`,
+//! `fibonacci`, `table:<points>,<points>,…` or `expr:<expression>`, where
+//! the expression is written in terms of the match count `n`:
+//!
+//! ```text
+//! sum     := product { ( "+" | "-" ) product }
+//! product := unary { ( "*" | "/" | "%" ) unary }
+//! unary   := "-" unary | power
+//! power   := atom [ "^" unary ]
+//! atom    := number | "n" | "(" sum ")"
+//! ```
+//!
+//! `^` binds tighter than a leading `-` and groups to the right, so
+//! `-2^n` is `-(2^n)` and `2^2^n` is `2^(2^n)`.
+//!
+//! ```text
+//! expr:n * (n + 1) / 2
+//! expr:10 * n - 5
+//! table:0,1,3,7
+//! ```
+
+use std::fmt;
+use std::io::{Error, ErrorKind};
+use std::str::FromStr;
+
+use crate::Card;
+
+/// Points for a card with `matches` matches, or `None` if the rule cannot
+/// give them, e.g. because of an overflow or a division by zero.
+pub trait Score {
+    fn score(&self, matches: usize) -> Option<i64>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Scoring {
+    /// 1 point for the first match, doubled for every other: 0, 1, 2, 4, 8, …
+    Doubling,
+    /// 1 point per match: 0, 1, 2, 3, 4, …
+    Linear,
+    /// Fibonacci numbers from 1 and 2: 0, 1, 2, 3, 5, 8, …
+    Fibonacci,
+    /// The points for 0, 1, 2, … matches. Counts past the end of the table
+    /// score its last entry.
+    Table(Vec<i64>),
+    Expression(Expr),
+}
+
+impl Score for Scoring {
+    fn score(&self, matches: usize) -> Option<i64> {
+        match self {
+            Scoring::Doubling => match matches {
+                0 => Some(0),
+                n => 2i64.checked_pow(u32::try_from(n - 1).ok()?),
+            },
+            Scoring::Linear => i64::try_from(matches).ok(),
+            Scoring::Fibonacci => {
+                let (mut a, mut b) = (0i64, 1i64);
+                for _ in 0..matches {
+                    (a, b) = (b, a.checked_add(b)?);
+                }
+                // Skip the second 1 so the scores keep growing.
+                if matches >= 2 {
+                    Some(b)
+                } else {
+                    Some(a)
+                }
+            }
+            Scoring::Table(points) => points.get(matches).or(points.last()).copied(),
+            Scoring::Expression(expr) => expr.eval(i64::try_from(matches).ok()?),
+        }
+    }
+}
+
+impl<F: Fn(usize) -> Option<i64>> Score for F {
+    fn score(&self, matches: usize) -> Option<i64> {
+        self(matches)
+    }
+}
+
+/// Total points of `cards` under `scoring` (part 1), or `None` if a card
+/// cannot be scored or the sum overflows.
+pub fn total_points<S: Score + ?Sized>(cards: &[Card], scoring: &S) -> Option<i64> {
+    cards.iter().try_fold(0i64, |total, card| {
+        total.checked_add(scoring.score(card.matches())?)
+    })
+}
+
+impl FromStr for Scoring {
+    type Err = ScoringError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, argument) = match s.split_once(':') {
+            Some((name, argument)) => (name.trim(), Some(argument)),
+            None => (s.trim(), None),
+        };
+        match (name, argument) {
+            ("doubling", None) => Ok(Scoring::Doubling),
+            ("linear", None) => Ok(Scoring::Linear),
+            ("fibonacci", None) => Ok(Scoring::Fibonacci),
+            ("table", Some(points)) => {
+                let points = points
+                    .split(',')
+                    .map(|entry| {
+                        entry
+                            .trim()
+                            .parse::<i64>()
+                            .map_err(|_| ScoringError(format!("invalid points `{}`", entry.trim())))
+                    })
+                    .collect::<Result<Vec<i64>, ScoringError>>()?;
+                Ok(Scoring::Table(points))
+            }
+            ("expr", Some(expr)) => Ok(Scoring::Expression(expr.parse()?)),
+            ("doubling" | "linear" | "fibonacci", Some(_)) => {
+                Err(ScoringError(format!("`{}` takes no argument", name)))
+            }
+            ("table" | "expr", None) => {
+                Err(ScoringError(format!("expected `{}:<argument>`", name)))
+            }
+            _ => Err(ScoringError(format!("unknown scoring rule `{}`", name))),
+        }
+    }
+}
+
+/// An arithmetic expression over the match count `n`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expr(Node);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Number(i64),
+    Matches,
+    Neg(Box<Node>),
+    Binary(Op, Box<Node>, Box<Node>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+}
+
+impl Expr {
+    /// The value with `n` set to `matches`, or `None` on overflow, division
+    /// by zero or a negative exponent.
+    pub fn eval(&self, matches: i64) -> Option<i64> {
+        self.0.eval(matches)
+    }
+}
+
+impl Node {
+    fn eval(&self, n: i64) -> Option<i64> {
+        match self {
+            Node::Number(value) => Some(*value),
+            Node::Matches => Some(n),
+            Node::Neg(inner) => inner.eval(n)?.checked_neg(),
+            Node::Binary(op, left, right) => {
+                let (left, right) = (left.eval(n)?, right.eval(n)?);
+                match op {
+                    Op::Add => left.checked_add(right),
+                    Op::Sub => left.checked_sub(right),
+                    Op::Mul => left.checked_mul(right),
+                    Op::Div => left.checked_div(right),
+                    Op::Rem => left.checked_rem(right),
+                    Op::Pow => left.checked_pow(u32::try_from(right).ok()?),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Number(i64),
+    Matches,
+    Symbol(char),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(number) => write!(f, "{}", number),
+            Token::Matches => write!(f, "n"),
+            Token::Symbol(symbol) => write!(f, "{}", symbol),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, ScoringError> {
+    let mut tokens = Vec::new();
+    let mut rest = input.trim_start();
+    while let Some(c) = rest.chars().next() {
+        let len = if c.is_ascii_digit() {
+            let len = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let number = rest[..len]
+                .parse()
+                .map_err(|_| ScoringError(format!("number `{}` is too large", &rest[..len])))?;
+            tokens.push(Token::Number(number));
+            len
+        } else if c == 'n' {
+            tokens.push(Token::Matches);
+            1
+        } else if "+-*/%^()".contains(c) {
+            tokens.push(Token::Symbol(c));
+            1
+        } else {
+            return Err(ScoringError(format!("unexpected character `{}`", c)));
+        };
+        rest = rest[len..].trim_start();
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Result<Token, ScoringError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or_else(|| ScoringError("unexpected end of expression".to_string()))?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    /// Consumes the next token if it is one of `symbols`.
+    fn eat_symbol(&mut self, symbols: &str) -> Option<char> {
+        match self.peek() {
+            Some(&Token::Symbol(symbol)) if symbols.contains(symbol) => {
+                self.position += 1;
+                Some(symbol)
+            }
+            _ => None,
+        }
+    }
+
+    fn sum(&mut self) -> Result<Node, ScoringError> {
+        let mut left = self.product()?;
+        while let Some(symbol) = self.eat_symbol("+-") {
+            let op = if symbol == '+' { Op::Add } else { Op::Sub };
+            left = Node::Binary(op, Box::new(left), Box::new(self.product()?));
+        }
+        Ok(left)
+    }
+
+    fn product(&mut self) -> Result<Node, ScoringError> {
+        let mut left = self.unary()?;
+        while let Some(symbol) = self.eat_symbol("*/%") {
+            let op = match symbol {
+                '*' => Op::Mul,
+                '/' => Op::Div,
+                _ => Op::Rem,
+            };
+            left = Node::Binary(op, Box::new(left), Box::new(self.unary()?));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Node, ScoringError> {
+        if self.eat_symbol("-").is_some() {
+            return Ok(Node::Neg(Box::new(self.unary()?)));
+        }
+        self.power()
+    }
+
+    fn power(&mut self) -> Result<Node, ScoringError> {
+        let base = self.atom()?;
+        if self.eat_symbol("^").is_some() {
+            return Ok(Node::Binary(
+                Op::Pow,
+                Box::new(base),
+                Box::new(self.unary()?),
+            ));
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<Node, ScoringError> {
+        match self.next()? {
+            Token::Number(value) => Ok(Node::Number(value)),
+            Token::Matches => Ok(Node::Matches),
+            Token::Symbol('(') => {
+                let inner = self.sum()?;
+                match self.next()? {
+                    Token::Symbol(')') => Ok(inner),
+                    found => Err(ScoringError(format!("expected `)`, found `{}`", found))),
+                }
+            }
+            found => Err(ScoringError(format!(
+                "expected a number, `n` or `(`, found `{}`",
+                found
+            ))),
+        }
+    }
+}
+
+impl FromStr for Expr {
+    type Err = ScoringError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            position: 0,
+        };
+        let node = parser.sum()?;
+        if let Some(token) = parser.peek() {
+            return Err(ScoringError(format!("unexpected `{}`", token)));
+        }
+        Ok(Expr(node))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoringError(String);
+
+impl fmt::Display for ScoringError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ScoringError {}
+
+impl From<ScoringError> for Error {
+    fn from(err: ScoringError) -> Self {
+        Error::new(ErrorKind::InvalidInput, err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::tests::EXAMPLE;
+    use crate::parse_cards;
+
+    fn scores(rule: &str) -> Vec<Option<i64>> {
+        let scoring = rule.parse::<Scoring>().unwrap();
+        (0..7).map(|n| scoring.score(n)).collect()
+    }
+
+    #[test]
+    fn test_doubling() {
+        assert_eq!(Scoring::Doubling.score(4), Some(8));
+        assert_eq!(Scoring::Doubling.score(2), Some(2));
+        assert_eq!(Scoring::Doubling.score(1), Some(1));
+        assert_eq!(Scoring::Doubling.score(0), Some(0));
+        assert_eq!(Scoring::Doubling.score(64), None);
+
+        let cards = parse_cards(EXAMPLE).unwrap();
+        assert_eq!(total_points(&cards, &Scoring::Doubling), Some(13));
+    }
+
+    #[test]
+    fn test_builtin_rules() {
+        let some = |values: &[i64]| values.iter().copied().map(Some).collect::<Vec<_>>();
+        assert_eq!(scores("linear"), some(&[0, 1, 2, 3, 4, 5, 6]));
+        assert_eq!(scores("fibonacci"), some(&[0, 1, 2, 3, 5, 8, 13]));
+        assert_eq!(scores("table:0, 1, 3, 7"), some(&[0, 1, 3, 7, 7, 7, 7]));
+        assert_eq!(
+            scores("expr:n * (n + 1) / 2"),
+            some(&[0, 1, 3, 6, 10, 15, 21])
+        );
+        assert_eq!(
+            scores("expr:-2^n + 1"),
+            some(&[0, -1, -3, -7, -15, -31, -63])
+        );
+        assert_eq!(scores("expr:6 / n")[0], None);
+        assert_eq!(scores("expr:2 ^ (1 - n)")[2], None);
+    }
+
+    #[test]
+    fn test_closure_and_totals() {
+        let cards = parse_cards(EXAMPLE).unwrap();
+        let squares = |n: usize| Some((n * n) as i64);
+        assert_eq!(total_points(&cards, &squares), Some(16 + 4 + 4 + 1));
+        assert_eq!(
+            total_points(&cards, &"expr:10 * n - 5".parse::<Scoring>().unwrap()),
+            Some(35 + 15 + 15 + 5 - 5 - 5)
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = |rule: &str| rule.parse::<Scoring>().unwrap_err().to_string();
+        assert_eq!(err("squares"), "unknown scoring rule `squares`");
+        assert_eq!(err("linear:2"), "`linear` takes no argument");
+        assert_eq!(err("table"), "expected `table:<argument>`");
+        assert_eq!(err("table:1,x"), "invalid points `x`");
+        assert_eq!(err("expr:n +"), "unexpected end of expression");
+        assert_eq!(err("expr:(n"), "unexpected end of expression");
+        assert_eq!(err("expr:n n"), "unexpected `n`");
+        assert_eq!(err("expr:m"), "unexpected character `m`");
+        assert_eq!(err("expr:*2"), "expected a number, `n` or `(`, found `*`");
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::VecDeque;
+use std::io::prelude::*;
+use std::io::{Error, ErrorKind};
+
+use crate::Card;
+
+/// The part 2 cascade fed one card at a time.
+///
+/// A card only ever wins copies of the next `matches` cards, so the only
+/// state kept is a ring buffer of copies already won by the cards still to
+/// come. It never holds more entries than the largest match count seen so
+/// far, however long the table. Copies won past the last card stay in the
+/// buffer and are never counted, as in [`crate::card_counts`].
+#[derive(Debug, Clone, Default)]
+pub struct Cascade {
+    /// Copies won so far by the next card, the one after it, and so on.
+    pending: VecDeque<u128>,
+    cards: usize,
+    total: u128,
+}
+
+impl Cascade {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of cards added so far.
+    pub fn cards(&self) -> usize {
+        self.cards
+    }
+
+    /// Scratchcards held so far, copies included.
+    pub fn total(&self) -> u128 {
+        self.total
+    }
+
+    /// Adds the next card in the table and returns how many copies of it are
+    /// held, or `None` if a count overflows a `u128`. After an overflow the
+    /// cascade is left as it was.
+    pub fn add(&mut self, card: &Card) -> Option<u128> {
+        let matches = card.matches();
+        let copies = self.pending.front().copied().unwrap_or(0).checked_add(1)?;
+        let total = self.total.checked_add(copies)?;
+
+        // Check every addition first so an overflow changes nothing.
+        let overflows = self
+            .pending
+            .iter()
+            .skip(1)
+            .take(matches)
+            .any(|count| count.checked_add(copies).is_none());
+        if overflows {
+            return None;
+        }
+
+        self.pending.pop_front();
+        if self.pending.len() < matches {
+            self.pending.resize(matches, 0);
+        }
+        for counter in self.pending.range_mut(..matches) {
+            *counter += copies;
+        }
+        self.cards += 1;
+        self.total = total;
+        Some(copies)
+    }
+}
+
+/// Parses each non-blank line of `reader` as a card and calls `on_card` with
+/// the card, its copies and the running total of scratchcards.
+pub fn stream_cards<R, F>(reader: R, mut on_card: F) -> Result<u128, Error>
+where
+    R: BufRead,
+    F: FnMut(&Card, u128, u128) -> Result<(), Error>,
+{
+    let mut cascade = Cascade::new();
+    for (row, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let card = line.parse::<Card>().map_err(|err| {
+            Error::new(ErrorKind::InvalidData, format!("line {}: {}", row + 1, err))
+        })?;
+        let copies = cascade.add(&card).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("line {}: scratchcard total overflows u128", row + 1),
+            )
+        })?;
+        on_card(&card, copies, cascade.total())?;
+    }
+    Ok(cascade.total())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::tests::EXAMPLE;
+    use crate::{card_counts, parse_cards};
+
+    #[test]
+    fn test_stream_example() {
+        let mut updates = Vec::new();
+        let total = stream_cards(EXAMPLE.as_bytes(), |card, copies, total| {
+            updates.push((card.id, copies, total));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(
+            updates,
+            vec![
+                (1, 1, 1),
+                (2, 2, 3),
+                (3, 4, 7),
+                (4, 8, 15),
+                (5, 14, 29),
+                (6, 1, 30)
+            ]
+        );
+        assert_eq!(total, 30);
+    }
+
+    #[test]
+    fn test_buffer_stays_small() {
+        // Every card matches three numbers, so each holds the sum of the
+        // three before it, and the buffer never grows past three entries.
+        let card = "Card 1: 1 2 3 | 1 2 3".parse::<Card>().unwrap();
+        let cards: Vec<Card> = (1..=60).map(|id| Card { id, ..card.clone() }).collect();
+        let expected = card_counts(&cards).unwrap();
+
+        let mut cascade = Cascade::new();
+        for (card, expected) in cards.iter().zip(expected) {
+            assert_eq!(cascade.add(card), Some(expected));
+            assert!(cascade.pending.len() <= 3);
+        }
+        assert_eq!(cascade.cards(), 60);
+    }
+
+    #[test]
+    fn test_matches_whole_table() {
+        let cards = parse_cards(
+            "Card 1: 1 2 3 4 5 | 1 2 3 4 5
+Card 2: 1 | 2
+Card 3: 1 2 | 1 2
+Card 4: 7 | 7
+Card 5: 1 2 3 | 1 2 9
+Card 6: 4 | 4
+Card 7: 4 5 | 4 5",
+        )
+        .unwrap();
+        let mut cascade = Cascade::new();
+        let copies: Vec<u128> = cards
+            .iter()
+            .map(|card| cascade.add(card).unwrap())
+            .collect();
+        assert_eq!(copies, card_counts(&cards).unwrap());
+        assert_eq!(cascade.total(), copies.iter().sum::<u128>());
+    }
+
+    #[test]
+    fn test_overflow_changes_nothing() {
+        // Card `i` of 129 matches every later card and holds `2^i` copies,
+        // so the last card's `2^128` copies do not fit.
+        let mut cascade = Cascade::new();
+        for i in 0..129 {
+            let numbers: Vec<usize> = (1..129 - i).collect();
+            let card = Card {
+                id: i + 1,
+                winning: numbers.clone(),
+                numbers,
+            };
+            assert_eq!(cascade.add(&card), (i < 128).then(|| 1 << i));
+        }
+        assert_eq!(cascade.cards(), 128);
+        assert_eq!(cascade.total(), u128::MAX);
+    }
+
+    #[test]
+    fn test_stream_errors() {
+        let err = stream_cards("Card 1: 1 | 1\n\nCard x: 1 | 1\n".as_bytes(), |_, _, _| {
+            Ok(())
+        })
+        .unwrap_err();
+        assert_eq!(err.to_string(), "line 3: invalid card id `x`");
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::Card;
+
+/// Something about a card table that parses fine but is probably a mistake.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// `number` is listed more than once among the winning numbers of
+    /// `card`. It still only counts as one match.
+    DuplicateWinning { card: usize, number: usize },
+    /// Two cards share `id`; the later one is at 1-based position `position`
+    /// in the table.
+    DuplicateId { id: usize, position: usize },
+    /// Cards should be numbered 1, 2, 3, … in table order.
+    Gap { expected: usize, found: usize },
+    /// `card` wins copies of the next `matches` cards, but only `remaining`
+    /// cards follow it.
+    WinsPastEnd {
+        card: usize,
+        matches: usize,
+        remaining: usize,
+    },
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::DuplicateWinning { card, number } => {
+                write!(f, "card {} lists winning number {} twice", card, number)
+            }
+            Issue::DuplicateId { id, position } => {
+                write!(f, "card {} appears again at position {}", id, position)
+            }
+            Issue::Gap { expected, found } => {
+                write!(f, "expected card {} next, found card {}", expected, found)
+            }
+            Issue::WinsPastEnd {
+                card,
+                matches,
+                remaining,
+            } => write!(
+                f,
+                "card {} wins {} cards but only {} follow it",
+                card, matches, remaining
+            ),
+        }
+    }
+}
+
+/// Every issue in `cards`, in table order.
+pub fn validate(cards: &[Card]) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut seen: HashMap<usize, usize> = HashMap::new();
+    let mut expected = 1;
+
+    for (index, card) in cards.iter().enumerate() {
+        let mut winning = HashSet::new();
+        let mut repeated = HashSet::new();
+        for &number in &card.winning {
+            if !winning.insert(number) && repeated.insert(number) {
+                issues.push(Issue::DuplicateWinning {
+                    card: card.id,
+                    number,
+                });
+            }
+        }
+
+        // A repeated id is reported once as such, not also as a gap.
+        if seen.insert(card.id, index).is_some() {
+            issues.push(Issue::DuplicateId {
+                id: card.id,
+                position: index + 1,
+            });
+        } else {
+            if card.id != expected {
+                issues.push(Issue::Gap {
+                    expected,
+                    found: card.id,
+                });
+            }
+            expected = card.id + 1;
+        }
+
+        let matches = card.matches();
+        let remaining = cards.len() - index - 1;
+        if matches > remaining {
+            issues.push(Issue::WinsPastEnd {
+                card: card.id,
+                matches,
+                remaining,
+            });
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::tests::EXAMPLE;
+    use crate::parse_cards;
+
+    #[test]
+    fn test_example_is_clean() {
+        assert_eq!(validate(&parse_cards(EXAMPLE).unwrap()), vec![]);
+    }
+
+    #[test]
+    fn test_issues() {
+        let cards = parse_cards(
+            "Card 1: 4 4 4 2 | 1 2 3
+Card 3: 7 8 | 7
+Card 3: 1 | 1
+Card 4: 1 | 2
+Card 6: 1 2 | 1 2",
+        )
+        .unwrap();
+        assert_eq!(
+            validate(&cards),
+            vec![
+                Issue::DuplicateWinning { card: 1, number: 4 },
+                Issue::Gap {
+                    expected: 2,
+                    found: 3
+                },
+                Issue::DuplicateId { id: 3, position: 3 },
+                Issue::Gap {
+                    expected: 5,
+                    found: 6
+                },
+                Issue::WinsPastEnd {
+                    card: 6,
+                    matches: 2,
+                    remaining: 0
+                },
+            ]
+        );
+        assert_eq!(
+            validate(&cards)[3].to_string(),
+            "expected card 5 next, found card 6"
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use std::fmt;
+use std::io::{Error, ErrorKind};
+use std::ops::Range;
+use std::str::FromStr;
+
+/// One `destination source length` row of a map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeMap {
+    pub destination_range: usize,
+    pub source_range: usize,
+    pub range_length: usize,
+}
+
+impl RangeMap {
+    /// The values this row maps. The end saturates rather than overflow.
+    pub fn source(&self) -> Range<usize> {
+        self.source_range..self.source_range.saturating_add(self.range_length)
+    }
+
+    /// The values this row maps to. The end saturates rather than overflow.
+    pub fn destination(&self) -> Range<usize> {
+        self.destination_range..self.destination_range.saturating_add(self.range_length)
+    }
+
+    /// Where `value` goes, if this row maps it. Like [`RangeMap::destination`]
+    /// this saturates rather than overflow.
+    pub fn map(&self, value: usize) -> Option<usize> {
+        self.source().contains(&value).then(|| {
+            self.destination_range
+                .saturating_add(value - self.source_range)
+        })
+    }
+}
+
+impl FromStr for RangeMap {
+    type Err = ParseAlmanacError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_numbers(s)?[..] {
+            [destination_range, source_range, range_length] => Ok(RangeMap {
+                destination_range,
+                source_range,
+                range_length,
+            }),
+            _ => Err(ParseAlmanacError::MalformedRow(s.trim().to_string())),
+        }
+    }
+}
+
+/// One `<source>-to-<destination> map:` section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stage {
+    /// The header without ` map:`, e.g. `seed-to-soil`.
+    pub name: String,
+    pub maps: Vec<RangeMap>,
+}
+
+impl Stage {
+    /// Where `value` goes: the first row covering it decides, and a value no
+    /// row covers stays as it is.
+    pub fn map(&self, value: usize) -> usize {
+        self.maps
+            .iter()
+            .find_map(|row| row.map(value))
+            .unwrap_or(value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Almanac {
+    pub seeds: Vec<usize>,
+    pub stages: Vec<Stage>,
+}
+
+impl Almanac {
+    /// Runs `seed` through every stage in turn.
+    pub fn location(&self, seed: usize) -> usize {
+        self.stages
+            .iter()
+            .fold(seed, |value, stage| stage.map(value))
+    }
+
+    /// The seeds line read as `start length` pairs (part 2). A trailing
+    /// unpaired number is ignored; [`crate::check`] rejects it for
+    /// [`crate::Seeds::Ranges`].
+    pub fn seed_ranges(&self) -> Vec<Range<usize>> {
+        self.seeds
+            .chunks_exact(2)
+            .map(|pair| pair[0]..pair[0].saturating_add(pair[1]))
+            .collect()
+    }
+}
+
+impl FromStr for Almanac {
+    type Err = ParseAlmanacError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut sections = split_sections(s).into_iter();
+
+        let seeds = sections
+            .next()
+            .and_then(|lines| lines.first()?.strip_prefix("seeds:"))
+            .ok_or(ParseAlmanacError::MissingSeeds)?;
+        let seeds = parse_numbers(seeds)?;
+
+        let stages = sections
+            .map(|lines| {
+                let name = lines[0]
+                    .strip_suffix("map:")
+                    .map(str::trim)
+                    .ok_or_else(|| ParseAlmanacError::MalformedHeader(lines[0].to_string()))?;
+                let maps = lines[1..]
+                    .iter()
+                    .map(|line| line.parse::<RangeMap>())
+                    .collect::<Result<Vec<RangeMap>, ParseAlmanacError>>()?;
+                Ok(Stage {
+                    name: name.to_string(),
+                    maps,
+                })
+            })
+            .collect::<Result<Vec<Stage>, ParseAlmanacError>>()?;
+
+        Ok(Almanac { seeds, stages })
+    }
+}
+
+/// Groups the trimmed, non-blank lines of `s` into blank-line separated
+/// sections.
+fn split_sections(s: &str) -> Vec<Vec<&str>> {
+    let mut sections: Vec<Vec<&str>> = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    for line in s.lines().map(str::trim) {
+        if line.is_empty() {
+            if !current.is_empty() {
+                sections.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        sections.push(current);
+    }
+    sections
+}
+
+fn parse_numbers(s: &str) -> Result<Vec<usize>, ParseAlmanacError> {
+    s.split_whitespace()
+        .map(|number| {
+            number
+                .parse::<usize>()
+                .map_err(|_| ParseAlmanacError::InvalidNumber(number.to_string()))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseAlmanacError {
+    MissingSeeds,
+    MalformedHeader(String),
+    MalformedRow(String),
+    InvalidNumber(String),
+}
+
+impl fmt::Display for ParseAlmanacError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseAlmanacError::MissingSeeds => write!(f, "expected a `seeds:` line first"),
+            ParseAlmanacError::MalformedHeader(header) => {
+                write!(f, "expected `<name> map:`, found `{}`", header)
+            }
+            ParseAlmanacError::MalformedRow(row) => {
+                write!(
+                    f,
+                    "expected `<destination> <source> <length>`, found `{}`",
+                    row
+                )
+            }
+            ParseAlmanacError::InvalidNumber(number) => write!(f, "invalid number `{}`", number),
+        }
+    }
+}
+
+impl std::error::Error for ParseAlmanacError {}
+
+impl From<ParseAlmanacError> for Error {
+    fn from(err: ParseAlmanacError) -> Self {
+        Error::new(ErrorKind::InvalidData, err)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn test_parse_example() {
+        let almanac = EXAMPLE.parse::<Almanac>().unwrap();
+        assert_eq!(almanac.seeds, vec![79, 14, 55, 13]);
+        assert_eq!(almanac.stages.len(), 7);
+        assert_eq!(almanac.stages[0].name, "seed-to-soil");
+        assert_eq!(
+            almanac.stages[0].maps[1],
+            RangeMap {
+                destination_range: 52,
+                source_range: 50,
+                range_length: 48
+            }
+        );
+        assert_eq!(almanac.seed_ranges(), vec![79..93, 55..68]);
+    }
+
+    #[test]
+    fn test_locations() {
+        let almanac = EXAMPLE.parse::<Almanac>().unwrap();
+        let locations: Vec<usize> = almanac
+            .seeds
+            .iter()
+            .map(|&seed| almanac.location(seed))
+            .collect();
+        assert_eq!(locations, vec![82, 43, 86, 35]);
+    }
+
+    #[test]
+    fn test_map_saturates() {
+        let row = RangeMap {
+            destination_range: usize::MAX - 2,
+            source_range: 0,
+            range_length: 5,
+        };
+        assert_eq!(row.map(2), Some(usize::MAX));
+        assert_eq!(row.map(4), Some(usize::MAX));
+        assert_eq!(row.map(5), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "soil: 1".parse::<Almanac>(),
+            Err(ParseAlmanacError::MissingSeeds)
+        );
+        assert_eq!(
+            "seeds: 1 x".parse::<Almanac>(),
+            Err(ParseAlmanacError::InvalidNumber("x".to_string()))
+        );
+        assert_eq!(
+            "seeds: 1\n\nseed-to-soil\n1 2 3".parse::<Almanac>(),
+            Err(ParseAlmanacError::MalformedHeader(
+                "seed-to-soil".to_string()
+            ))
+        );
+        assert_eq!(
+            "seeds: 1\n\nseed-to-soil map:\n1 2".parse::<Almanac>(),
+            Err(ParseAlmanacError::MalformedRow("1 2".to_string()))
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use std::env;
+use std::io::Error;
+
+use day_05::{check, fingerprint, Almanac, Piecewise, Seeds};
+
+/// Usage: `compose [--cache path] [--seed n]... [input]`
+///
+/// Prints the almanac's stages composed into one `seed-to-location` map, or
+/// with `--seed` the location of each given seed. `--cache` reuses the map
+/// saved at `path` if it was built from the same input, and saves it there
+/// otherwise.
+fn main() -> Result<(), Error> {
+    let mut cache = None;
+    let mut seeds: Vec<usize> = Vec::new();
+    let positional = aoc_cli::parse(env::args().skip(1), |values| {
+        match values.flag() {
+            "--cache" => cache = Some(values.value()?),
+            "--seed" => seeds.push(values.parse()?),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    let path = aoc_cli::input_path(positional, "src/day-05.txt")?;
+
+    let input = aoc_cli::read_input(&path)?;
+    let compose = || -> Result<Piecewise, Error> {
+        let almanac = input.parse::<Almanac>()?;
+        for issue in check(&almanac, Seeds::Values)? {
+            eprintln!("warning: {}", issue);
+        }
+        Ok(almanac.compose())
+    };
+    let composed = match &cache {
+        Some(cache) => match Piecewise::load(cache, fingerprint(&input))? {
+            Some(composed) => composed,
+            None => {
+                let composed = compose()?;
+                composed.save(cache, fingerprint(&input))?;
+                composed
+            }
+        },
+        None => compose()?,
+    };
+
+    if seeds.is_empty() {
+        print!("seed-to-location map:\n{}", composed);
+    }
+    for seed in seeds {
+        println!("{} {}", seed, composed.map(seed));
+    }
+    Ok(())
+}
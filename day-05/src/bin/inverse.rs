@@ -0,0 +1,63 @@
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::ops::Range;
+
+use day_05::{check, Almanac, Seeds};
+
+/// Usage: `inverse [--location n|start..end] [input]`
+///
+/// Prints every seed range whose locations fall in the given ones, each
+/// marked with whether the seeds line plants it. Without `--location` it
+/// asks for the lowest location of part 2.
+fn main() -> Result<(), Error> {
+    let mut locations = None;
+    let positional = aoc_cli::parse(env::args().skip(1), |values| {
+        match values.flag() {
+            "--location" => locations = Some(parse_locations(&values.value()?)?),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    let path = aoc_cli::input_path(positional, "src/day-05.txt")?;
+
+    let almanac = aoc_cli::read_input(&path)?.parse::<Almanac>()?;
+    for issue in check(&almanac, Seeds::Ranges)? {
+        eprintln!("warning: {}", issue);
+    }
+    let locations = match locations {
+        Some(locations) => locations,
+        None => {
+            let lowest = almanac
+                .lowest_location(&almanac.seed_ranges())
+                .ok_or_else(|| {
+                    Error::new(ErrorKind::InvalidData, "the seed ranges hold no seeds")
+                })?;
+            lowest..lowest + 1
+        }
+    };
+
+    let seeds = almanac.seeds_for(locations);
+    for (range, planted) in almanac.mark_planted(&seeds) {
+        let status = if planted { "planted" } else { "not planted" };
+        println!("{}..{} {}", range.start, range.end, status);
+    }
+    Ok(())
+}
+
+/// Reads a single location `n` or a half-open range `start..end`.
+fn parse_locations(value: &str) -> Result<Range<usize>, Error> {
+    let invalid = || {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("invalid location `{}`", value),
+        )
+    };
+    let number = |s: &str| s.trim().parse::<usize>().map_err(|_| invalid());
+    match value.split_once("..") {
+        Some((start, end)) => Ok(number(start)?..number(end)?),
+        None => {
+            let location = number(value)?;
+            Ok(location..location.checked_add(1).ok_or_else(invalid)?)
+        }
+    }
+}
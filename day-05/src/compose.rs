@@ -0,0 +1,311 @@
+use std::fmt;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::ops::Range;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::{Almanac, ParseAlmanacError, RangeMap, Stage};
+
+/// A whole chain of stages as one function: sorted, non-overlapping rows,
+/// with every value outside them mapping to itself. Lookups are a binary
+/// search over the rows.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Piecewise {
+    rows: Vec<RangeMap>,
+}
+
+impl Piecewise {
+    pub fn identity() -> Self {
+        Self::default()
+    }
+
+    /// The function `stage` computes. Where rows overlap the earlier one
+    /// wins, as in [`Stage::map`], so the result may have more rows than the
+    /// stage.
+    pub fn from_stage(stage: &Stage) -> Self {
+        let mut claimed: Vec<Range<usize>> = Vec::new();
+        let mut rows = Vec::new();
+        for row in stage.maps.iter().filter(|row| row.range_length > 0) {
+            let mut free = vec![row.source()];
+            for taken in &claimed {
+                free = free
+                    .into_iter()
+                    .flat_map(|range| {
+                        [
+                            range.start..range.end.min(taken.start),
+                            range.start.max(taken.end)..range.end,
+                        ]
+                    })
+                    .filter(|range| !range.is_empty())
+                    .collect();
+            }
+            for range in free {
+                rows.push(RangeMap {
+                    destination_range: row
+                        .map(range.start)
+                        .expect("free ranges lie within the row's source"),
+                    source_range: range.start,
+                    range_length: range.len(),
+                });
+            }
+            claimed.push(row.source());
+        }
+        Self::from_disjoint(rows)
+    }
+
+    /// Sorts non-overlapping `rows`, drops those that map values to
+    /// themselves and joins neighbors that continue each other.
+    fn from_disjoint(mut rows: Vec<RangeMap>) -> Self {
+        rows.retain(|row| row.range_length > 0 && row.destination_range != row.source_range);
+        rows.sort_by_key(|row| row.source_range);
+
+        let mut joined: Vec<RangeMap> = Vec::with_capacity(rows.len());
+        for row in rows {
+            match joined.last_mut() {
+                Some(last)
+                    if last.source().end == row.source_range
+                        && last.destination().end == row.destination_range =>
+                {
+                    last.range_length += row.range_length;
+                }
+                _ => joined.push(row),
+            }
+        }
+        Piecewise { rows: joined }
+    }
+
+    pub fn rows(&self) -> &[RangeMap] {
+        &self.rows
+    }
+
+    pub fn map(&self, value: usize) -> usize {
+        let after = self.rows.partition_point(|row| row.source_range <= value);
+        after
+            .checked_sub(1)
+            .and_then(|index| self.rows[index].map(value))
+            .unwrap_or(value)
+    }
+
+    /// Cuts `range` where this function changes rows, as `(piece, where the
+    /// piece starts after mapping)` in order.
+    pub fn segments(&self, range: Range<usize>) -> Vec<(Range<usize>, usize)> {
+        let mut segments = Vec::new();
+        let mut start = range.start;
+        let first = self.rows.partition_point(|row| row.source().end <= start);
+        for row in &self.rows[first..] {
+            if start >= range.end {
+                break;
+            }
+            let source = row.source();
+            if source.start > start {
+                let gap = start..source.start.min(range.end);
+                segments.push((gap.clone(), gap.start));
+                start = gap.end;
+            }
+            if start < range.end {
+                let inside = start..source.end.min(range.end);
+                let mapped = row
+                    .map(inside.start)
+                    .expect("`inside` lies within the row's source");
+                segments.push((inside.clone(), mapped));
+                start = inside.end;
+            }
+        }
+        if start < range.end {
+            segments.push((start..range.end, start));
+        }
+        segments
+    }
+
+    /// This function followed by `next`.
+    pub fn then(&self, next: &Piecewise) -> Piecewise {
+        let mut rows = Vec::new();
+        for (piece, mapped) in self.segments(0..usize::MAX) {
+            let shifted = mapped..mapped.saturating_add(piece.len());
+            for (part, destination_range) in next.segments(shifted) {
+                rows.push(RangeMap {
+                    destination_range,
+                    source_range: piece.start + (part.start - mapped),
+                    range_length: part.len(),
+                });
+            }
+        }
+        Self::from_disjoint(rows)
+    }
+
+    /// The lowest value any number in `ranges` maps to.
+    pub fn lowest(&self, ranges: &[Range<usize>]) -> Option<usize> {
+        ranges
+            .iter()
+            .flat_map(|range| self.segments(range.clone()))
+            .map(|(_, mapped)| mapped)
+            .min()
+    }
+
+    /// Writes this function to `path` in almanac format, under a comment
+    /// recording `fingerprint`.
+    pub fn save<P: AsRef<Path>>(&self, path: P, fingerprint: u64) -> Result<(), Error> {
+        fs::write(
+            path,
+            format!(
+                "# fingerprint {:016x}\nseed-to-location map:\n{}",
+                fingerprint, self
+            ),
+        )
+    }
+
+    /// Reads a function written by [`Piecewise::save`]. Returns `None` if
+    /// there is no file at `path` or it was saved for another fingerprint.
+    pub fn load<P: AsRef<Path>>(path: P, fingerprint: u64) -> Result<Option<Self>, Error> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let expected = format!("# fingerprint {:016x}", fingerprint);
+        if contents.lines().next() != Some(expected.as_str()) {
+            return Ok(None);
+        }
+        Ok(Some(contents.parse()?))
+    }
+}
+
+/// One `destination source length` row per line, in source order.
+impl fmt::Display for Piecewise {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in &self.rows {
+            writeln!(
+                f,
+                "{} {} {}",
+                row.destination_range, row.source_range, row.range_length
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads rows in almanac format, skipping blank lines, `#` comments and a
+/// `... map:` header. Overlapping rows resolve as in a stage.
+impl FromStr for Piecewise {
+    type Err = ParseAlmanacError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let maps = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.ends_with("map:"))
+            .map(|line| line.parse::<RangeMap>())
+            .collect::<Result<Vec<RangeMap>, ParseAlmanacError>>()?;
+        Ok(Self::from_stage(&Stage {
+            name: String::new(),
+            maps,
+        }))
+    }
+}
+
+impl Almanac {
+    /// Every stage composed into one seed-to-location function.
+    pub fn compose(&self) -> Piecewise {
+        self.stages
+            .iter()
+            .fold(Piecewise::identity(), |composed, stage| {
+                composed.then(&Piecewise::from_stage(stage))
+            })
+    }
+}
+
+/// A stable FNV-1a hash of `input`, used to tell whether a saved
+/// [`Piecewise`] still belongs to an almanac.
+pub fn fingerprint(input: &str) -> u64 {
+    input.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::almanac::tests::EXAMPLE;
+    use crate::test_support::{brute_force_locations, check_random_almanacs};
+    use std::env;
+
+    #[test]
+    fn test_compose_example() {
+        let almanac = EXAMPLE.parse::<Almanac>().unwrap();
+        let composed = almanac.compose();
+        for seed in 0..120 {
+            assert_eq!(composed.map(seed), almanac.location(seed), "seed {}", seed);
+        }
+        assert_eq!(composed.lowest(&almanac.seed_ranges()), Some(46));
+
+        // Sorted and disjoint, so a binary search finds the only candidate.
+        for pair in composed.rows().windows(2) {
+            assert!(pair[0].source().end <= pair[1].source_range);
+        }
+    }
+
+    #[test]
+    fn test_overlapping_stage() {
+        let stage = Stage {
+            name: "a-to-b".to_string(),
+            maps: "100 10 5\n200 0 20\n5 5 1"
+                .lines()
+                .map(|line| line.parse().unwrap())
+                .collect(),
+        };
+        let piecewise = Piecewise::from_stage(&stage);
+        assert_eq!(piecewise.to_string(), "200 0 10\n100 10 5\n215 15 5\n");
+        for value in 0..30 {
+            assert_eq!(piecewise.map(value), stage.map(value));
+        }
+    }
+
+    #[test]
+    fn test_segments() {
+        let piecewise: Piecewise = "50 10 5\n70 20 5".parse().unwrap();
+        assert_eq!(
+            piecewise.segments(8..22),
+            vec![(8..10, 8), (10..15, 50), (15..20, 15), (20..22, 70)]
+        );
+        assert_eq!(piecewise.segments(30..40), vec![(30..40, 30)]);
+        assert_eq!(piecewise.lowest(&[12..14, 21..24]), Some(52));
+    }
+
+    #[test]
+    fn test_print_and_parse_round_trip() {
+        let composed = EXAMPLE.parse::<Almanac>().unwrap().compose();
+        let printed = composed.to_string();
+        assert_eq!(printed.parse::<Piecewise>(), Ok(composed));
+    }
+
+    #[test]
+    fn test_random_almanacs_match_location() {
+        check_random_almanacs(48, |almanac, rng| {
+            let composed = almanac.compose();
+            for seed in 0..150 {
+                assert_eq!(composed.map(seed), almanac.location(seed));
+            }
+            let ranges: Vec<Range<usize>> = (0..1 + rng.below(3))
+                .map(|_| rng.range(120, 1..41))
+                .collect();
+            assert_eq!(
+                composed.lowest(&ranges),
+                Some(brute_force_locations(almanac, &ranges)[0].start)
+            );
+        });
+    }
+
+    #[test]
+    fn test_cache() {
+        let composed = EXAMPLE.parse::<Almanac>().unwrap().compose();
+        let path = env::temp_dir().join(format!("day-05-compose-{}.txt", std::process::id()));
+        let key = fingerprint(EXAMPLE);
+
+        assert_eq!(Piecewise::load(&path, key).unwrap(), None);
+        composed.save(&path, key).unwrap();
+        assert_eq!(Piecewise::load(&path, key).unwrap(), Some(composed));
+        assert_eq!(Piecewise::load(&path, key ^ 1).unwrap(), None);
+        fs::remove_file(&path).unwrap();
+    }
+}
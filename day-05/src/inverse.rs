@@ -0,0 +1,149 @@
+use std::ops::Range;
+
+use crate::{normalize, Almanac, Piecewise};
+
+impl Piecewise {
+    /// Every value that maps into `ranges`, sorted and merged. Values outside
+    /// every row map to themselves, so they are found too, unless a row
+    /// already moved them elsewhere.
+    pub fn preimage(&self, ranges: &[Range<usize>]) -> Vec<Range<usize>> {
+        let targets = normalize(ranges.to_vec());
+        let mut found = Vec::new();
+        for (piece, mapped) in self.segments(0..usize::MAX) {
+            let image = mapped..mapped.saturating_add(piece.len());
+            for target in &targets {
+                let hit = image.start.max(target.start)..image.end.min(target.end);
+                if !hit.is_empty() {
+                    let start = piece.start + (hit.start - mapped);
+                    found.push(start..start + hit.len());
+                }
+            }
+        }
+        normalize(found)
+    }
+}
+
+impl Almanac {
+    /// Every seed whose location falls in `locations`, found by undoing
+    /// the stages from last to first. Seeds the seeds line never plants are
+    /// included; see [`Almanac::mark_planted`].
+    pub fn seeds_for(&self, locations: Range<usize>) -> Vec<Range<usize>> {
+        self.stages
+            .iter()
+            .rev()
+            .fold(normalize(vec![locations]), |ranges, stage| {
+                Piecewise::from_stage(stage).preimage(&ranges)
+            })
+    }
+
+    /// Cuts `seeds` where the seed ranges of the seeds line begin and end,
+    /// marking each piece with whether those ranges plant it.
+    pub fn mark_planted(&self, seeds: &[Range<usize>]) -> Vec<(Range<usize>, bool)> {
+        let planted = normalize(self.seed_ranges());
+        let mut marked = Vec::new();
+        for range in normalize(seeds.to_vec()) {
+            let mut start = range.start;
+            let first = planted.partition_point(|seeds| seeds.end <= start);
+            for seeds in &planted[first..] {
+                if seeds.start >= range.end {
+                    break;
+                }
+                if seeds.start > start {
+                    marked.push((start..seeds.start, false));
+                }
+                let inside = start.max(seeds.start)..seeds.end.min(range.end);
+                start = inside.end;
+                marked.push((inside, true));
+            }
+            if start < range.end {
+                marked.push((start..range.end, false));
+            }
+        }
+        marked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::almanac::tests::EXAMPLE;
+    use crate::test_support::{brute_force_seeds, check_random_almanacs};
+    use crate::{RangeMap, Stage};
+
+    #[test]
+    fn test_example() {
+        let almanac = EXAMPLE.parse::<Almanac>().unwrap();
+        assert_eq!(almanac.seeds_for(35..36), vec![13..14]);
+        assert_eq!(almanac.seeds_for(46..47), vec![82..83]);
+        assert_eq!(almanac.seeds_for(0..100), vec![0..100]);
+
+        // The lowest location of part 2 comes from seed 82 alone, which the
+        // second seed range, 79..93, plants.
+        let seeds = almanac.seeds_for(46..47);
+        assert_eq!(almanac.mark_planted(&seeds), vec![(82..83, true)]);
+    }
+
+    #[test]
+    fn test_mark_planted() {
+        let almanac = Almanac {
+            seeds: vec![10, 5, 20, 5],
+            stages: Vec::new(),
+        };
+        assert_eq!(
+            almanac.mark_planted(&[0..12, 14..30]),
+            vec![
+                (0..10, false),
+                (10..12, true),
+                (14..15, true),
+                (15..20, false),
+                (20..25, true),
+                (25..30, false)
+            ]
+        );
+        assert_eq!(
+            almanac.mark_planted(&[26..28, 30..40]),
+            vec![(26..28, false), (30..40, false)]
+        );
+    }
+
+    #[test]
+    fn test_identity_fallback_is_shadowed() {
+        // 0..5 moves to 10..15, so nothing lands on 0..5 any more, while
+        // 10..15 is reached from both 0..5 and itself.
+        let stage = Stage {
+            name: "a-to-b".to_string(),
+            maps: vec![RangeMap {
+                destination_range: 10,
+                source_range: 0,
+                range_length: 5,
+            }],
+        };
+        let almanac = Almanac {
+            seeds: Vec::new(),
+            stages: vec![stage],
+        };
+        assert_eq!(almanac.seeds_for(0..5), Vec::<Range<usize>>::new());
+        assert_eq!(almanac.seeds_for(10..15), vec![0..5, 10..15]);
+    }
+
+    #[test]
+    fn test_random_almanacs_match_brute_force() {
+        check_random_almanacs(49, |almanac, rng| {
+            let locations = rng.range(120, 1..21);
+
+            // Every row lies below 130, so seeds from there on keep their
+            // value and none past 140 can reach `locations`.
+            let seeds = almanac.seeds_for(locations.clone());
+            let mut below: Vec<Range<usize>> = seeds
+                .iter()
+                .map(|range| range.start.min(200)..range.end.min(200))
+                .collect();
+            below.retain(|range| !range.is_empty());
+            assert_eq!(below, brute_force_seeds(almanac, &locations, 200));
+            assert_eq!(
+                almanac.compose().preimage(std::slice::from_ref(&locations)),
+                seeds
+            );
+        });
+    }
+}
@@ -0,0 +1,12 @@
+mod almanac;
+mod compose;
+mod inverse;
+mod ranges;
+#[cfg(test)]
+pub(crate) mod test_support;
+mod validate;
+
+pub use almanac::{Almanac, ParseAlmanacError, RangeMap, Stage};
+pub use compose::{fingerprint, Piecewise};
+pub use ranges::normalize;
+pub use validate::{check, validate, Issue, Seeds};
@@ -0,0 +1,153 @@
+use std::ops::Range;
+
+use crate::{Almanac, Stage};
+
+/// Sorts `ranges`, drops empty ones and merges any that overlap or touch.
+pub fn normalize(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.retain(|range| !range.is_empty());
+    ranges.sort_by_key(|range| range.start);
+
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+impl Stage {
+    /// Maps every value in `ranges` at once, the same way [`Stage::map`]
+    /// maps one.
+    ///
+    /// Each row in turn takes the parts of the still unmapped ranges inside
+    /// its source and shifts them to its destination, leaving the pieces on
+    /// either side for the rows after it. Whatever no row takes passes
+    /// through unchanged.
+    pub fn map_ranges(&self, ranges: &[Range<usize>]) -> Vec<Range<usize>> {
+        let mut unmapped: Vec<Range<usize>> = ranges.to_vec();
+        let mut mapped: Vec<Range<usize>> = Vec::new();
+
+        for row in &self.maps {
+            let source = row.source();
+            let mut rest = Vec::with_capacity(unmapped.len());
+            for range in unmapped {
+                let inside = range.start.max(source.start)..range.end.min(source.end);
+                if inside.is_empty() {
+                    rest.push(range);
+                    continue;
+                }
+                let start = row
+                    .map(inside.start)
+                    .expect("`inside` lies within the row's source");
+                mapped.push(start..start.saturating_add(inside.len()));
+                rest.push(range.start..inside.start);
+                rest.push(inside.end..range.end);
+            }
+            unmapped = rest.into_iter().filter(|range| !range.is_empty()).collect();
+        }
+
+        mapped.extend(unmapped);
+        normalize(mapped)
+    }
+}
+
+impl Almanac {
+    /// Every location reachable from a seed in `ranges`.
+    pub fn map_ranges(&self, ranges: &[Range<usize>]) -> Vec<Range<usize>> {
+        self.stages
+            .iter()
+            .fold(normalize(ranges.to_vec()), |ranges, stage| {
+                stage.map_ranges(&ranges)
+            })
+    }
+
+    /// The lowest location of any seed in `ranges`, or `None` if they hold
+    /// no seeds.
+    pub fn lowest_location(&self, ranges: &[Range<usize>]) -> Option<usize> {
+        self.map_ranges(ranges).first().map(|range| range.start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::almanac::tests::EXAMPLE;
+    use crate::test_support::{brute_force_locations, check_random_almanacs};
+    use crate::RangeMap;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize(vec![5..7, 0..2, 3..3, 1..4, 7..9, 12..15]),
+            vec![0..4, 5..9, 12..15]
+        );
+    }
+
+    #[test]
+    fn test_example() {
+        let almanac = EXAMPLE.parse::<Almanac>().unwrap();
+        let ranges = almanac.seed_ranges();
+        assert_eq!(almanac.lowest_location(&ranges), Some(46));
+        assert_eq!(brute_force_locations(&almanac, &ranges)[0].start, 46);
+
+        let seeds: Vec<Range<usize>> = almanac.seeds.iter().map(|&s| s..s + 1).collect();
+        assert_eq!(almanac.lowest_location(&seeds), Some(35));
+        assert_eq!(almanac.lowest_location(&[]), None);
+    }
+
+    #[test]
+    fn test_overlapping_rows_follow_row_order() {
+        let stage = Stage {
+            name: "a-to-b".to_string(),
+            maps: vec![
+                RangeMap {
+                    destination_range: 100,
+                    source_range: 10,
+                    range_length: 5,
+                },
+                RangeMap {
+                    destination_range: 200,
+                    source_range: 0,
+                    range_length: 20,
+                },
+            ],
+        };
+        assert_eq!(
+            stage.map_ranges(std::slice::from_ref(&(5..25))),
+            vec![20..25, 100..105, 205..210, 215..220]
+        );
+    }
+
+    #[test]
+    fn test_destination_ending_at_usize_max() {
+        let stage = Stage {
+            name: "a-to-b".to_string(),
+            maps: vec![RangeMap {
+                destination_range: usize::MAX - 3,
+                source_range: 0,
+                range_length: 5,
+            }],
+        };
+        assert_eq!(
+            stage.map_ranges(&[2..5, 10..12]),
+            vec![10..12, usize::MAX - 1..usize::MAX]
+        );
+    }
+
+    #[test]
+    fn test_random_almanacs_match_brute_force() {
+        check_random_almanacs(47, |almanac, rng| {
+            let ranges: Vec<Range<usize>> = (0..1 + rng.below(4))
+                .map(|_| rng.range(120, 0..40))
+                .collect();
+            let expected = brute_force_locations(almanac, &ranges);
+            assert_eq!(
+                almanac.lowest_location(&ranges),
+                expected.first().map(|range| range.start)
+            );
+            assert_eq!(almanac.map_ranges(&ranges), expected);
+        });
+    }
+}
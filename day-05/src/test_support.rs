@@ -0,0 +1,70 @@
+use std::ops::Range;
+
+use aoc_testing::Lcg;
+
+use crate::{normalize, Almanac, RangeMap, Stage};
+
+/// Runs `check` on 200 random almanacs, drawn from a generator seeded with
+/// `seed` that `check` may keep drawing from.
+pub(crate) fn check_random_almanacs<F>(seed: u64, mut check: F)
+where
+    F: FnMut(&Almanac, &mut Lcg),
+{
+    let mut rng = Lcg::new(seed);
+    for _ in 0..200 {
+        let almanac = random_almanac(&mut rng);
+        check(&almanac, &mut rng);
+    }
+}
+
+/// One to four stages of up to four rows each, every row within `0..130`,
+/// and no seeds.
+fn random_almanac(rng: &mut Lcg) -> Almanac {
+    let stages = (0..1 + rng.below(4))
+        .map(|i| Stage {
+            name: format!("stage-{}", i),
+            maps: (0..rng.below(5))
+                .map(|_| RangeMap {
+                    destination_range: rng.below(100),
+                    source_range: rng.below(100),
+                    range_length: rng.below(30),
+                })
+                .collect(),
+        })
+        .collect();
+    Almanac {
+        seeds: Vec::new(),
+        stages,
+    }
+}
+
+/// Every location the seeds in `ranges` reach, found by running them
+/// through [`Almanac::location`] one by one.
+pub(crate) fn brute_force_locations(
+    almanac: &Almanac,
+    ranges: &[Range<usize>],
+) -> Vec<Range<usize>> {
+    normalize(
+        ranges
+            .iter()
+            .flat_map(|range| range.clone())
+            .map(|seed| almanac.location(seed))
+            .map(|location| location..location + 1)
+            .collect(),
+    )
+}
+
+/// The seeds below `bound` whose location falls in `locations`, found one
+/// seed at a time.
+pub(crate) fn brute_force_seeds(
+    almanac: &Almanac,
+    locations: &Range<usize>,
+    bound: usize,
+) -> Vec<Range<usize>> {
+    normalize(
+        (0..bound)
+            .filter(|&seed| locations.contains(&almanac.location(seed)))
+            .map(|seed| seed..seed + 1)
+            .collect(),
+    )
+}
@@ -0,0 +1,265 @@
+use std::fmt;
+use std::io::{Error, ErrorKind};
+use std::ops::Range;
+
+use crate::Almanac;
+
+/// How the seeds line is read: as seeds (part 1), or as `start length`
+/// pairs (part 2).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Seeds {
+    Values,
+    Ranges,
+}
+
+/// Something about an almanac that parses fine but is probably a mistake.
+///
+/// Rows are counted from 1 within their stage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// The seeds line has an odd number of values, so read as ranges its
+    /// last one has no length.
+    OddSeeds { count: usize },
+    /// `start + length` does not fit in a `usize`. `section` is a stage name,
+    /// or `seeds` for the `row`-th pair of the seeds line.
+    Overflow {
+        section: String,
+        row: usize,
+        start: usize,
+        length: usize,
+    },
+    /// The row maps no values.
+    EmptyRange { stage: String, row: usize },
+    /// Rows `first` and `second` both map the values in `overlap`, so only
+    /// `first` ever applies to them.
+    OverlappingSources {
+        stage: String,
+        first: usize,
+        second: usize,
+        overlap: Range<usize>,
+    },
+    /// Rows `first` and `second` both map values into `overlap`, so those
+    /// values have more than one source.
+    OverlappingDestinations {
+        stage: String,
+        first: usize,
+        second: usize,
+        overlap: Range<usize>,
+    },
+}
+
+impl Issue {
+    /// Whether the issue leaves the almanac without a meaning when its seeds
+    /// are read as `seeds`, rather than just looking wrong: the engine
+    /// saturates at `usize::MAX` instead of overflowing, so its answers for
+    /// such rows are not the almanac's, and seed ranges would silently drop
+    /// an unpaired last seed.
+    pub fn is_fatal(&self, seeds: Seeds) -> bool {
+        match self {
+            Issue::Overflow { .. } => true,
+            Issue::OddSeeds { .. } => seeds == Seeds::Ranges,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::OddSeeds { count } => write!(
+                f,
+                "the seeds line has {} values, so the last one has no length",
+                count
+            ),
+            Issue::Overflow {
+                section,
+                row,
+                start,
+                length,
+            } => write!(
+                f,
+                "{} row {}: {} + {} overflows",
+                section, row, start, length
+            ),
+            Issue::EmptyRange { stage, row } => {
+                write!(f, "{} row {} has length 0", stage, row)
+            }
+            Issue::OverlappingSources {
+                stage,
+                first,
+                second,
+                overlap,
+            } => write!(
+                f,
+                "{} rows {} and {} both map {}..{}",
+                stage, first, second, overlap.start, overlap.end
+            ),
+            Issue::OverlappingDestinations {
+                stage,
+                first,
+                second,
+                overlap,
+            } => write!(
+                f,
+                "{} rows {} and {} both map into {}..{}",
+                stage, first, second, overlap.start, overlap.end
+            ),
+        }
+    }
+}
+
+/// Every issue in `almanac`: the seeds line first, then each stage row by
+/// row.
+pub fn validate(almanac: &Almanac) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    if almanac.seeds.len() % 2 == 1 {
+        issues.push(Issue::OddSeeds {
+            count: almanac.seeds.len(),
+        });
+    }
+    for (index, pair) in almanac.seeds.chunks_exact(2).enumerate() {
+        if pair[0].checked_add(pair[1]).is_none() {
+            issues.push(Issue::Overflow {
+                section: "seeds".to_string(),
+                row: index + 1,
+                start: pair[0],
+                length: pair[1],
+            });
+        }
+    }
+
+    for stage in &almanac.stages {
+        for (index, row) in stage.maps.iter().enumerate() {
+            if row.range_length == 0 {
+                issues.push(Issue::EmptyRange {
+                    stage: stage.name.clone(),
+                    row: index + 1,
+                });
+            }
+            for start in [row.source_range, row.destination_range] {
+                if start.checked_add(row.range_length).is_none() {
+                    issues.push(Issue::Overflow {
+                        section: stage.name.clone(),
+                        row: index + 1,
+                        start,
+                        length: row.range_length,
+                    });
+                }
+            }
+
+            // Ranges that overflow are cut off at `usize::MAX`, which is
+            // enough to tell whether they overlap anything.
+            for (earlier, other) in stage.maps[..index].iter().enumerate() {
+                let sources = overlap(other.source(), row.source());
+                if !sources.is_empty() {
+                    issues.push(Issue::OverlappingSources {
+                        stage: stage.name.clone(),
+                        first: earlier + 1,
+                        second: index + 1,
+                        overlap: sources,
+                    });
+                }
+                let destinations = overlap(other.destination(), row.destination());
+                if !destinations.is_empty() {
+                    issues.push(Issue::OverlappingDestinations {
+                        stage: stage.name.clone(),
+                        first: earlier + 1,
+                        second: index + 1,
+                        overlap: destinations,
+                    });
+                }
+            }
+        }
+    }
+    issues
+}
+
+/// The issues in `almanac` worth a warning, or an `InvalidData` error for
+/// the first one fatal when its seeds are read as `seeds`.
+pub fn check(almanac: &Almanac, seeds: Seeds) -> Result<Vec<Issue>, Error> {
+    let issues = validate(almanac);
+    match issues.iter().find(|issue| issue.is_fatal(seeds)) {
+        Some(fatal) => Err(Error::new(ErrorKind::InvalidData, fatal.to_string())),
+        None => Ok(issues),
+    }
+}
+
+fn overlap(a: Range<usize>, b: Range<usize>) -> Range<usize> {
+    a.start.max(b.start)..a.end.min(b.end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::almanac::tests::EXAMPLE;
+
+    #[test]
+    fn test_example_is_clean() {
+        let almanac = EXAMPLE.parse::<Almanac>().unwrap();
+        assert_eq!(validate(&almanac), Vec::new());
+    }
+
+    #[test]
+    fn test_only_overflow_is_fatal() {
+        let almanac = format!("seeds: 0 5 1\n\na-to-b map:\n{} 0 5\n0 0 0", usize::MAX - 1)
+            .parse::<Almanac>()
+            .unwrap();
+        let fatal: Vec<bool> = validate(&almanac)
+            .iter()
+            .map(|issue| issue.is_fatal(Seeds::Values))
+            .collect();
+        assert_eq!(fatal, vec![false, true, false]);
+    }
+
+    #[test]
+    fn test_odd_seeds_are_fatal_as_ranges() {
+        let almanac = "seeds: 79 14 55\n\na-to-b map:\n50 98 2"
+            .parse::<Almanac>()
+            .unwrap();
+        assert_eq!(
+            check(&almanac, Seeds::Values).unwrap(),
+            vec![Issue::OddSeeds { count: 3 }]
+        );
+        let err = check(&almanac, Seeds::Ranges).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            "the seeds line has 3 values, so the last one has no length"
+        );
+    }
+
+    #[test]
+    fn test_issues() {
+        let almanac = format!(
+            "seeds: 1 2 {} 1 7
+
+seed-to-soil map:
+100 10 5
+200 12 5
+102 50 1
+0 0 0
+{} 1 2",
+            usize::MAX,
+            usize::MAX - 1
+        )
+        .parse::<Almanac>()
+        .unwrap();
+
+        let issues: Vec<String> = validate(&almanac)
+            .iter()
+            .map(|issue| issue.to_string())
+            .collect();
+        assert_eq!(
+            issues,
+            vec![
+                "the seeds line has 5 values, so the last one has no length".to_string(),
+                format!("seeds row 2: {} + 1 overflows", usize::MAX),
+                "seed-to-soil rows 1 and 2 both map 12..15".to_string(),
+                "seed-to-soil rows 1 and 3 both map into 102..103".to_string(),
+                "seed-to-soil row 4 has length 0".to_string(),
+                format!("seed-to-soil row 5: {} + 2 overflows", usize::MAX - 1),
+            ]
+        );
+    }
+}